@@ -0,0 +1,100 @@
+//! Advent of Code 2015, day 1: Not Quite Lisp.
+
+#![warn(missing_docs)]
+
+use std::fmt;
+
+use aoc_common::{Class, Generated, Rng, Span};
+
+/// Why the directions cannot be followed.
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// Santa never reaches floor -1.
+    NeverEntersTheBasement,
+    /// A character other than `(` or `)`.
+    InvalidCharacter(char, Span)
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::NeverEntersTheBasement => write!(f, "Basement is never reached."),
+            Error::InvalidCharacter(c, _) => write!(f, "Encountered an invalid character: {}.", c),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::NeverEntersTheBasement => Class::Solve,
+            Error::InvalidCharacter(..) => Class::Parse,
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::InvalidCharacter(_, span) => Some(*span),
+            _ => None,
+        }
+    }
+}
+
+/// Climbs without ever going below the ground floor, steps down into the
+/// basement and then wanders about.
+fn random_input(rng: &mut Rng) -> Generated {
+    let floor = |steps: &[isize]| steps.iter().sum::<isize>();
+    let mut steps: Vec<isize> = Vec::new();
+    for _ in 0..rng.size(0..=200) {
+        steps.push(if floor(&steps) == 0 || rng.chance(0.5) { 1 } else { -1 });
+    }
+    while floor(&steps) >= 0 {
+        steps.push(-1);
+    }
+    let basement = steps.len();
+    for _ in 0..rng.size(0..=200) {
+        steps.push(if rng.chance(0.5) { 1 } else { -1 });
+    }
+    let text: String = steps.iter().map(|&step| if step > 0 { '(' } else { ')' }).collect();
+    Generated::answered(text + "\n", floor(&steps), basement)
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day01;
+
+impl aoc_common::Solution for Day01 {
+    type Input = Vec<isize>;
+    type Error = Error;
+    type Part1 = isize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<isize>, Error> {
+        let floors = input.trim();
+        floors.char_indices().map(|(k, direction)|
+            match direction {
+                '(' => Ok(1),
+                ')' => Ok(-1),
+                err => Err(Error::InvalidCharacter(err, Span::at(input, floors, k)))
+            }
+        ).collect()
+    }
+
+    fn part1(steps: &Vec<isize>) -> Result<isize, Error> {
+        Ok(steps.iter().sum())
+    }
+
+    fn part2(steps: &Vec<isize>) -> Result<usize, Error> {
+        Ok(steps.iter()
+            .scan(0, |floor, step| {
+                *floor += step;
+                Some(*floor)
+            })
+            .enumerate()
+            .find(|(_, floor)| *floor < 0)
+            .ok_or(Error::NeverEntersTheBasement)?.0 + 1)
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+}
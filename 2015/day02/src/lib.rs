@@ -0,0 +1,114 @@
+//! Advent of Code 2015, day 2: I Was Told There Would Be No Math.
+
+#![warn(missing_docs)]
+
+use std::fmt;
+
+use aoc_common::{Class, Generated, Rng, Span};
+
+/// Why a list of presents cannot be read.
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// A line that is not three dimensions like `2x3x4`.
+    InvalidBoxFormat(String, Span),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidBoxFormat(s, _) => write!(f, "This box has an unknown format: {}", s),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::InvalidBoxFormat(..) => Class::Parse,
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::InvalidBoxFormat(_, span) => Some(*span),
+        }
+    }
+}
+
+/// A present's dimensions in feet.
+#[derive(Debug, Copy, Clone)]
+pub struct Box {
+    length: usize,
+    width: usize,
+    height: usize,
+}
+
+impl Box {
+    fn new(text: &str, spec: &str) -> Result<Box, Error> {
+        let parsed: Option<Vec<usize>> = spec
+            .split('x').map(|p| p.parse().ok()).collect();
+        if let Some(p) = parsed {
+            if p.len() == 3 {
+                return Ok(Box { length: p[0], width: p[1], height: p[2] })
+            }
+        }
+        Err(Error::InvalidBoxFormat(spec.to_string(), Span::of(text, spec)))
+    }
+
+    fn ribbon(&self) -> usize {
+        let sides = [self.length, self.width, self.height];
+        let largest = sides.iter().max().unwrap();
+        let girth = sides.iter().sum::<usize>() - largest;
+        let bow = sides.iter().product::<usize>();
+        2 * girth + bow
+    }
+
+    fn wrapping(&self) -> usize {
+        let l = self.length;
+        let w = self.width;
+        let h = self.height;
+        let sides = [l*w, w*h, h*l];
+        let slack = sides.iter().min().unwrap();
+        2 * sides.iter().sum::<usize>() + slack
+    }
+}
+
+fn random_input(rng: &mut Rng) -> Generated {
+    let (mut paper, mut ribbon) = (0, 0);
+    let mut text = String::new();
+    for _ in 0..rng.size(1..=100) {
+        let mut sides = [rng.size(1..=30), rng.size(1..=30), rng.size(1..=30)];
+        text += &format!("{}x{}x{}\n", sides[0], sides[1], sides[2]);
+        sides.sort();
+        let [a, b, c] = sides;
+        paper += 2 * (a * b + b * c + c * a) + a * b;
+        ribbon += 2 * (a + b) + a * b * c;
+    }
+    Generated::answered(text, paper, ribbon)
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day02;
+
+impl aoc_common::Solution for Day02 {
+    type Input = Vec<Box>;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Box>, Error> {
+        aoc_common::trimmed(input).iter().map(|p| Box::new(input, p)).collect()
+    }
+
+    fn part1(boxes: &Vec<Box>) -> Result<usize, Error> {
+        Ok(boxes.iter().map(|b| b.wrapping()).sum())
+    }
+
+    fn part2(boxes: &Vec<Box>) -> Result<usize, Error> {
+        Ok(boxes.iter().map(|b| b.ribbon()).sum())
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+}
@@ -0,0 +1,119 @@
+//! Advent of Code 2020, day 1: Report Repair.
+
+#![warn(missing_docs)]
+
+use std::collections::HashSet;
+use std::fmt;
+
+use aoc_common::{Class, Generated, Rng, Span};
+
+/// Why the expense report cannot be fixed.
+#[derive(Debug)]
+pub enum Error {
+    /// A line that is not a non-negative integer.
+    ParsingError(Span),
+    /// No two, or no three, entries add up to 2020.
+    NoSolution,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::ParsingError(_) => write!(f, "Expected an expense as a positive integer."),
+            Error::NoSolution => write!(f, "No entries sum up to 2020."),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::ParsingError(..) => Class::Parse,
+            Error::NoSolution => Class::Solve,
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::ParsingError(span) => Some(*span),
+            _ => None,
+        }
+    }
+}
+
+/// A pair and a triple of entries that sum to 2020, hidden among entries
+/// too large to pair up with each other; inputs where some other entries
+/// also sum to 2020 are drawn again.
+fn random_input(rng: &mut Rng) -> Generated {
+    loop {
+        let a = rng.range(1..=2019) as u32;
+        let c = rng.range(1..=2018) as u32;
+        let d = rng.range(1..=(2019 - c as i64)) as u32;
+        let mut entries = vec![a, 2020 - a, c, d, 2020 - c - d];
+        entries.extend((0..rng.size(0..=60)).map(|_| rng.range(1011..=2019) as u32));
+        rng.shuffle(&mut entries);
+        let mut pairs = HashSet::new();
+        let mut triples = HashSet::new();
+        for &x in &entries {
+            for &y in &entries {
+                if x + y == 2020 {
+                    pairs.insert(x * y);
+                }
+                for &z in &entries {
+                    if x + y + z == 2020 {
+                        triples.insert(x * y * z);
+                    }
+                }
+            }
+        }
+        if pairs.len() == 1 && triples.len() == 1 {
+            let text: String = entries.iter().map(|n| format!("{}\n", n)).collect();
+            return Generated::answered(text, a * (2020 - a), c * d * (2020 - c - d));
+        }
+    }
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day01;
+
+impl aoc_common::Solution for Day01 {
+    type Input = Vec<u32>;
+    type Error = Error;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<u32>, Error> {
+        aoc_common::trimmed(input)
+            .iter()
+            .map(|line| line.parse().map_err(|_| Error::ParsingError(Span::of(input, line))))
+            .collect()
+    }
+
+    fn part1(expense_report: &Vec<u32>) -> Result<u32, Error> {
+        for &a in expense_report {
+            for &b in expense_report {
+                if a + b == 2020 {
+                    return Ok(a * b);
+                }
+            }
+        }
+        Err(Error::NoSolution)
+    }
+
+    fn part2(expense_report: &Vec<u32>) -> Result<u32, Error> {
+        for &a in expense_report {
+            for &b in expense_report {
+                for &c in expense_report {
+                    if a + b + c == 2020 {
+                        return Ok(a * b * c);
+                    }
+                }
+            }
+        }
+        Err(Error::NoSolution)
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+}
@@ -0,0 +1,135 @@
+//! Advent of Code 2020, day 2: Password Philosophy.
+
+#![warn(missing_docs)]
+
+use regex::Regex;
+use std::fmt;
+use aoc_common::{Class, Generated, Rng, Span};
+
+/// Why the password database cannot be read.
+#[derive(Debug)]
+pub enum Error {
+    /// A line that is not a policy followed by a password.
+    ParsingError(Span),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::ParsingError(_) => write!(f, "Expected a password entry like `1-3 a: abcde`."),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::ParsingError(..) => Class::Parse,
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::ParsingError(span) => Some(*span),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum PasswordValidationPolicy {
+    SledRentalPlace,
+    OfficialToboggan
+}
+
+/// A password with the policy it was set under.
+pub struct PasswordEntry {
+    min_count: usize,
+    max_count: usize,
+    character: char,
+    password : String,
+}
+
+impl PasswordEntry {
+    /// Both counts double as positions starting at 1, so neither may be 0.
+    fn new(line: &str) -> Option<PasswordEntry> {
+        let captures = Regex::new("(\\d+)-(\\d+)\\s(.):\\s(.*)")
+            .ok()?
+            .captures(line)?;
+        Some(PasswordEntry {
+            min_count: captures[1].parse().ok().filter(|&n| n > 0)?,
+            max_count: captures[2].parse().ok().filter(|&n| n > 0)?,
+            character: captures[3].chars().next()?,
+            password : captures[4].to_string()
+        })
+    }
+
+    fn char_count(&self) -> usize {
+        self.password
+            .chars()
+            .filter(|&x| x == self.character)
+            .count()
+    }
+ 
+    fn valid(&self, policy: PasswordValidationPolicy) -> bool {
+        match policy {
+            PasswordValidationPolicy::OfficialToboggan => {
+                (self.password.chars().nth(self.min_count-1) == Some(self.character)) ^
+                (self.password.chars().nth(self.max_count-1) == Some(self.character))
+            },
+            PasswordValidationPolicy::SledRentalPlace => {
+                let cc = self.char_count();
+                self.min_count <= cc && cc <= self.max_count
+            }
+        }
+    }
+}
+
+fn get_valid_password_count(entries: &[PasswordEntry], policy: PasswordValidationPolicy) -> usize {
+    entries.iter().filter(|entry| entry.valid(policy)).count()
+}
+
+fn random_input(rng: &mut Rng) -> Generated {
+    let letters = ['a', 'b', 'c', 'd'];
+    let (mut text, mut sled, mut toboggan) = (String::new(), 0, 0);
+    for _ in 0..rng.size(1..=100) {
+        let letter = *rng.pick(&letters);
+        let password: String = (0..rng.size(1..=12)).map(|_| *rng.pick(&letters)).collect();
+        let low = rng.size(1..=6);
+        let high = rng.size(low..=low + 6);
+        text += &format!("{}-{} {}: {}\n", low, high, letter, password);
+        let count = password.chars().filter(|&c| c == letter).count();
+        sled += (low..=high).contains(&count) as usize;
+        let at = |position: usize| password.chars().nth(position - 1) == Some(letter);
+        toboggan += (at(low) != at(high)) as usize;
+    }
+    Generated::answered(text, sled, toboggan)
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day02;
+
+impl aoc_common::Solution for Day02 {
+    type Input = Vec<PasswordEntry>;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<PasswordEntry>, Error> {
+        aoc_common::trimmed(input)
+            .into_iter()
+            .map(|line| PasswordEntry::new(line).ok_or(Error::ParsingError(Span::of(input, line))))
+            .collect()
+    }
+
+    fn part1(entries: &Vec<PasswordEntry>) -> Result<usize, Error> {
+        Ok(get_valid_password_count(entries, PasswordValidationPolicy::SledRentalPlace))
+    }
+
+    fn part2(entries: &Vec<PasswordEntry>) -> Result<usize, Error> {
+        Ok(get_valid_password_count(entries, PasswordValidationPolicy::OfficialToboggan))
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+}
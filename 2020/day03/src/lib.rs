@@ -0,0 +1,124 @@
+//! Advent of Code 2020, day 3: Toboggan Trajectory.
+
+#![warn(missing_docs)]
+
+use std::fmt;
+use aoc_common::{Class, Generated, Grid, GridError, Rng, Span};
+
+/// Why the map of trees cannot be read.
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// A square that is neither open nor a tree.
+    FileFormatError(Span),
+    /// A row that is shorter or longer than the first.
+    InconsistentLineLengths(Span),
+    /// The map has no rows.
+    EmptyMap,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::FileFormatError(_) => write!(f, "Expected `.` for open ground or `#` for a tree."),
+            Error::InconsistentLineLengths(_) => write!(f, "All lines of the map must have the same length."),
+            Error::EmptyMap => write!(f, "The map is empty."),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::FileFormatError(..) => Class::Parse,
+            Error::InconsistentLineLengths(..) => Class::Parse,
+            Error::EmptyMap => Class::Parse,
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::FileFormatError(span) => Some(*span),
+            Error::InconsistentLineLengths(span) => Some(*span),
+            _ => None,
+        }
+    }
+}
+
+impl From<GridError> for Error {
+    fn from(error: GridError) -> Self {
+        match error {
+            GridError::InvalidCharacter(_, span) => Error::FileFormatError(span),
+            GridError::RaggedRow(span) => Error::InconsistentLineLengths(span),
+            GridError::Empty => Error::EmptyMap,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum SquareType {
+    Open,
+    Tree
+}
+
+/// The slope of open squares and trees, repeating to the right.
+pub struct Area {
+    map: Grid<SquareType>
+}
+
+impl Area {
+    fn new(text: &str) -> Result<Self, Error> {
+        let map = Grid::parse(text, |c| match c {
+            '.' => Some(SquareType::Open),
+            '#' => Some(SquareType::Tree),
+             _  => None
+        })?;
+        Ok(Area{map})
+    }
+
+    fn count_trees(&self, right: usize, down: usize) -> usize {
+        (0..self.map.height())
+            .step_by(down)
+            .enumerate()
+            .filter(|&(k, y)| self.map.wrapped((k * right) as isize, y as isize) == Some(&SquareType::Tree))
+            .count()
+    }
+}
+
+fn random_input(rng: &mut Rng) -> Generated {
+    let (width, height) = (rng.size(1..=40), rng.size(1..=100));
+    let text = (0..height).map(|_| {
+        let row: String = (0..width).map(|_| if rng.chance(0.3) { '#' } else { '.' }).collect();
+        row + "\n"
+    }).collect();
+    Generated::text(text)
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day03;
+
+impl aoc_common::Solution for Day03 {
+    type Input = Area;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Area, Error> {
+        Area::new(input)
+    }
+
+    fn part1(area: &Area) -> Result<usize, Error> {
+        Ok(area.count_trees(3, 1))
+    }
+
+    fn part2(area: &Area) -> Result<usize, Error> {
+        let mut checksum: usize = 1;
+        for (right,down) in [(1,1),(3,1),(5,1),(7,1),(1,2)] {
+            checksum *= area.count_trees(right, down)
+        }
+        Ok(checksum)
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+}
@@ -0,0 +1,208 @@
+//! Advent of Code 2020, day 4: Passport Processing.
+
+#![warn(missing_docs)]
+
+use regex::Regex;
+use std::collections::HashMap;
+use std::fmt;
+use aoc_common::{Class, Generated, Rng};
+
+/// Why the passports cannot be validated.
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// A field pattern failed to compile; a bug, not bad input.
+    RegexError,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::RegexError => write!(f, "Invalid regular expression."),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::RegexError => Class::Solve,
+        }
+    }
+}
+
+/// The fields of one passport, by key.
+pub struct PassportData {
+    fields: HashMap<String, String>,
+}
+
+impl PassportData {
+    fn new(data: &str) -> Self {
+        PassportData {
+            fields: Regex::new(r"(?P<key>[a-z]{3}):(?P<value>[^ \n]+)")
+                .map_err(|_| Error::RegexError)
+                .unwrap()
+                .captures_iter(data)
+                .map(|c| (String::from(&c["key"]), String::from(&c["value"])))
+                .collect(),
+        }
+    }
+
+    fn is_valid_pt1(&self) -> bool {
+        let required_fields = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+        required_fields
+            .iter()
+            .all(|&key| self.fields.contains_key(key))
+    }
+
+    fn is_valid_pt2(&self) -> bool {
+        if !self.is_valid_pt1() {
+            return false;
+        } 
+
+        let is_valid_year = |key: &str, min: usize, max: usize| -> bool {
+            if let Ok(number) = self.fields[key].parse::<usize>() {
+                if number < min { return false; }
+                if number > max { return false; }
+                true
+            } else {
+                false
+            }
+        };
+
+        if !is_valid_year("byr", 1920, 2002) {
+            return false;
+        }
+        if !is_valid_year("iyr", 2010, 2020) {
+            return false;
+        }
+        if !is_valid_year("eyr", 2020, 2030) {
+            return false;
+        }
+
+        if let Some(caps) = Regex::new(r"(\d+)(cm|in)").unwrap().captures(&self.fields["hgt"]) {
+            let value: usize = caps[1].parse().unwrap();
+            let (min, max) = if &caps[2] == "cm" {(150, 193)} else {(59, 76)};
+            if value < min {
+                return false;
+            }
+            if value > max {
+                return false;
+            }
+        } else {
+            return false;
+        }
+
+        if !Regex::new(r"^#[0-9a-f]{6}$").unwrap().is_match(&self.fields["hcl"]) {
+            return false;
+        }
+
+        if !Regex::new(r"^(amb|blu|brn|gry|grn|hzl|oth)$").unwrap().is_match(&self.fields["ecl"]) {
+            return false;
+        }
+        
+        if !Regex::new(r"^\d{9}$").unwrap().is_match(&self.fields["pid"]) {
+            return false;
+        }
+
+        true
+    }
+}
+
+impl std::fmt::Display for PassportData {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let fields = self.fields.iter().map(|(key, value)| {
+            format!("{}: {}", key, value)
+        }).collect::<Vec<String>>().join("\n");
+        f.write_str(&fields)
+    }
+}
+
+const FIELDS: [&str; 8] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"];
+
+/// A value for `field` that is valid, or that breaks the rules if `valid`
+/// is false.
+fn random_value(rng: &mut Rng, field: &str, valid: bool) -> String {
+    let year = |rng: &mut Rng, min: i64, max: i64| {
+        if valid { rng.range(min..=max) } else if rng.chance(0.5) { min - rng.range(1..=50) } else { max + rng.range(1..=50) }
+    };
+    match field {
+        "byr" => year(rng, 1920, 2002).to_string(),
+        "iyr" => year(rng, 2010, 2020).to_string(),
+        "eyr" => year(rng, 2020, 2030).to_string(),
+        "hgt" if rng.chance(0.5) => format!("{}cm", year(rng, 150, 193)),
+        "hgt" if valid => format!("{}in", rng.range(59..=76)),
+        "hgt" => format!("{}", rng.range(59..=193)),
+        "hcl" if valid => format!("#{:06x}", rng.below(1 << 24)),
+        "hcl" => format!("#{:05x}", rng.below(1 << 20)),
+        "ecl" if valid => String::from(*rng.pick(&["amb", "blu", "brn", "gry", "grn", "hzl", "oth"])),
+        "ecl" => String::from(*rng.pick(&["red", "bl", "xyz"])),
+        "pid" if valid => format!("{:09}", rng.below(1_000_000_000)),
+        "pid" => format!("{:010}", rng.below(1_000_000_000)),
+        _ => rng.range(1..=350).to_string(),
+    }
+}
+
+/// Passports that are complete and valid, miss a required field, or are
+/// complete with one invalid value.
+fn random_input(rng: &mut Rng) -> Generated {
+    let (mut passports, mut complete, mut valid) = (Vec::new(), 0, 0);
+    for _ in 0..rng.size(1..=50) {
+        let mut fields: Vec<&str> = FIELDS.iter().copied().filter(|&f| f != "cid" || rng.chance(0.5)).collect();
+        let kind = rng.below(3);
+        let broken = match kind {
+            0 => None,
+            1 => {
+                fields.remove(rng.below(7) as usize);
+                None
+            },
+            _ => Some(FIELDS[rng.below(7) as usize]),
+        };
+        complete += (kind != 1) as usize;
+        valid += (kind == 0) as usize;
+        rng.shuffle(&mut fields);
+        let mut text = String::new();
+        for (k, field) in fields.iter().enumerate() {
+            if k > 0 {
+                text.push(if rng.chance(0.3) { '\n' } else { ' ' });
+            }
+            text += &format!("{}:{}", field, random_value(rng, field, broken != Some(field)));
+        }
+        passports.push(text);
+    }
+    Generated::answered(passports.join("\n\n") + "\n", complete, valid)
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day04;
+
+impl aoc_common::Solution for Day04 {
+    type Input = Vec<PassportData>;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<PassportData>, Error> {
+        Ok(aoc_common::paragraphs(input)
+            .into_iter()
+            .map(PassportData::new)
+            .collect())
+    }
+
+    fn part1(passport_data: &Vec<PassportData>) -> Result<usize, Error> {
+        Ok(passport_data
+            .iter()
+            .filter(|data| data.is_valid_pt1())
+            .count())
+    }
+
+    fn part2(passport_data: &Vec<PassportData>) -> Result<usize, Error> {
+        Ok(passport_data
+            .iter()
+            .filter(|data| data.is_valid_pt2())
+            .count())
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+}
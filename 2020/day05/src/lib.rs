@@ -0,0 +1,101 @@
+//! Advent of Code 2020, day 5: Binary Boarding.
+
+#![warn(missing_docs)]
+
+use std::fmt;
+
+use aoc_common::{Class, Generated, Rng, Span};
+
+/// Why the boarding passes do not lead to a seat.
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// A boarding pass with a character other than `F`, `B`, `L` or `R`.
+    FileFormatError(Span),
+    /// There are no boarding passes, or no seat is free between two taken ones.
+    NoSeatFound,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::FileFormatError(_) => write!(f, "Expected a boarding pass made of `F`, `B`, `L` and `R`."),
+            Error::NoSeatFound => write!(f, "No free seat found."),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::FileFormatError(..) => Class::Parse,
+            Error::NoSeatFound => Class::Solve,
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::FileFormatError(span) => Some(*span),
+            _ => None,
+        }
+    }
+}
+
+/// A block of consecutive seats with one missing from the middle.
+fn random_input(rng: &mut Rng) -> Generated {
+    let first = rng.size(8..=500);
+    let last = rng.size(first + 2..=1015);
+    let missing = rng.size(first + 1..=last - 1);
+    let mut ids: Vec<usize> = (first..=last).filter(|&id| id != missing).collect();
+    rng.shuffle(&mut ids);
+    let text = ids.iter().map(|id| {
+        let pass: String = (0..10).rev().map(|bit| {
+            let set = id >> bit & 1 == 1;
+            match (bit >= 3, set) {
+                (true, false) => 'F',
+                (true, true) => 'B',
+                (false, false) => 'L',
+                (false, true) => 'R',
+            }
+        }).collect();
+        pass + "\n"
+    }).collect();
+    Generated::answered(text, last, missing)
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day05;
+
+impl aoc_common::Solution for Day05 {
+    type Input = Vec<usize>;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<usize>, Error> {
+        let mut ids = aoc_common::trimmed(input)
+            .into_iter()
+            .map(|x| {
+                let binary = x.replace("F","0").replace("B","1").replace("L","0").replace("R","1");
+                usize::from_str_radix(&binary, 2).map_err(|_| Error::FileFormatError(Span::of(input, x)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        ids.sort();
+        ids.reverse();
+        Ok(ids)
+    }
+
+    fn part1(ids: &Vec<usize>) -> Result<usize, Error> {
+        ids.first().copied().ok_or(Error::NoSeatFound)
+    }
+
+    fn part2(ids: &Vec<usize>) -> Result<usize, Error> {
+        ids.windows(2)
+            .find(|pair| pair[1] + 2 == pair[0])
+            .map(|pair| pair[1] + 1)
+            .ok_or(Error::NoSeatFound)
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+}
@@ -0,0 +1,113 @@
+//! Advent of Code 2020, day 6: Custom Customs.
+
+#![warn(missing_docs)]
+
+use std::collections::HashSet;
+use std::convert::Infallible;
+use aoc_common::{Generated, Rng};
+
+trait IteratorCallback<'a>: Copy {
+    type Output: Iterator<Item = &'a char> + 'a;
+    fn call(self, a: &'a HashSet<char>, b: &'a HashSet<char>) -> Self::Output;
+}
+
+impl<'a, F, T> IteratorCallback<'a> for F
+where
+    F: Fn(&'a HashSet<char>, &'a HashSet<char>) -> T,
+    F: Copy,
+    T: 'a,
+    T: Iterator<Item = &'a char>,
+{
+    type Output = T;
+    fn call(self, a: &'a HashSet<char>, b: &'a HashSet<char>) -> T {
+        self(a, b)
+    }
+}
+
+fn apply<O>(lines: &[String], operation: O) -> usize
+where
+    O: for<'a> IteratorCallback<'a>,
+{
+    let mut counter = 0;
+    let mut accumulator: HashSet<char> = HashSet::new();
+    let mut group_started = false;
+    for line in lines {
+        if line.is_empty() {
+            counter += accumulator.len();
+            accumulator.clear();
+            group_started = false;
+        } else if group_started {
+            let mut answers = HashSet::new();
+            line.chars().for_each(|c| {
+                answers.insert(c);
+            });
+            accumulator = operation.call(&accumulator, &answers).copied().collect();
+        } else {
+            line.chars().for_each(|c| {
+                accumulator.insert(c);
+            });
+            group_started = true;
+        }
+    }
+    counter + accumulator.len()
+}
+
+/// Groups where the questions everyone answered and those only some
+/// answered are chosen first.
+fn random_input(rng: &mut Rng) -> Generated {
+    let (mut groups, mut anyone, mut everyone) = (Vec::new(), 0, 0);
+    let count = rng.size(1..=50);
+    while groups.len() < count {
+        let people = rng.size(1..=5);
+        let mut questions: Vec<char> = ('a'..='z').collect();
+        rng.shuffle(&mut questions);
+        questions.truncate(rng.size(1..=10));
+        let shared = if people == 1 { questions.len() } else { rng.size(0..=questions.len()) };
+        let mut answers = vec![questions[..shared].to_vec(); people];
+        for &question in &questions[shared..] {
+            let (yes, no) = (rng.size(0..=people - 1), rng.size(1..=people - 1));
+            for (k, person) in answers.iter_mut().enumerate() {
+                if k == yes || (k != (yes + no) % people && rng.chance(0.5)) {
+                    person.push(question);
+                }
+            }
+        }
+        if answers.iter().any(Vec::is_empty) {
+            continue;
+        }
+        anyone += questions.len();
+        everyone += shared;
+        let lines: Vec<String> = answers.into_iter().map(|mut person| {
+            rng.shuffle(&mut person);
+            person.into_iter().collect()
+        }).collect();
+        groups.push(lines.join("\n"));
+    }
+    Generated::answered(groups.join("\n\n") + "\n", anyone, everyone)
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day06;
+
+impl aoc_common::Solution for Day06 {
+    type Input = Vec<String>;
+    type Error = Infallible;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<String>, Infallible> {
+        Ok(input.split('\n').map(String::from).collect())
+    }
+
+    fn part1(lines: &Vec<String>) -> Result<usize, Infallible> {
+        Ok(apply(lines, HashSet::union))
+    }
+
+    fn part2(lines: &Vec<String>) -> Result<usize, Infallible> {
+        Ok(apply(lines, HashSet::intersection))
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+}
@@ -0,0 +1,89 @@
+//! Advent of Code 2021, day 1: Sonar Sweep.
+
+#![warn(missing_docs)]
+
+use itertools::Itertools;
+use std::fmt;
+use aoc_common::{Class, Generated, Rng, Span};
+
+/// Why the sonar sweep cannot be read.
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// A line that is not a depth.
+    InvalidInputError(Span),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidInputError(_) => write!(f, "Invalid file format; expected line-wise integer values."),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::InvalidInputError(..) => Class::Parse,
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::InvalidInputError(span) => Some(*span),
+        }
+    }
+}
+
+fn parse(text: &str) -> Result<Vec<usize>, Error> {
+    aoc_common::trimmed(text).into_iter()
+        .map(|x| x.parse().map_err(|_| Error::InvalidInputError(Span::of(text, x))))
+        .collect()
+}
+
+fn count<I: Iterator<Item=usize>>(measurements: I) -> usize {
+    measurements.tuple_windows().filter(|(a,b)| a < b).count()
+}
+
+/// A random walk of depths, counting the increases as it goes; a window of
+/// three grows exactly when the depth entering it exceeds the one leaving.
+fn random_input(rng: &mut Rng) -> Generated {
+    let mut depths = vec![rng.size(100..=200)];
+    let (mut increases, mut window_increases) = (0, 0);
+    for k in 1..rng.size(1..=300) {
+        let depth = (depths[k - 1] as i64 + rng.range(-10..=20)).max(0) as usize;
+        increases += (depth > depths[k - 1]) as usize;
+        if k >= 3 {
+            window_increases += (depth > depths[k - 3]) as usize;
+        }
+        depths.push(depth);
+    }
+    let text = depths.iter().map(|depth| format!("{}\n", depth)).collect();
+    Generated::answered(text, increases, window_increases)
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day01;
+
+impl aoc_common::Solution for Day01 {
+    type Input = Vec<usize>;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<usize>, Error> {
+        parse(input)
+    }
+
+    fn part1(parsed: &Vec<usize>) -> Result<usize, Error> {
+        Ok(count(parsed.iter().copied()))
+    }
+
+    fn part2(parsed: &Vec<usize>) -> Result<usize, Error> {
+        Ok(count(parsed.iter().copied().tuple_windows::<(_,_,_)>().map(|(a,b,c)| a + b + c)))
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+}
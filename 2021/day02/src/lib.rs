@@ -0,0 +1,169 @@
+//! Advent of Code 2021, day 2: Dive!
+
+#![warn(missing_docs)]
+
+use std::fmt;
+
+use aoc_common::{Class, Generated, Rng, Span};
+
+/// Why the planned course cannot be read.
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// A line that is not a direction followed by a distance.
+    InputParseError(Span),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InputParseError(_) => write!(f, "Expected a command like `forward 5`, `down 3` or `up 1`."),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::InputParseError(..) => Class::Parse,
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::InputParseError(span) => Some(*span),
+        }
+    }
+}
+
+/// One step of the planned course.
+#[derive(Debug, Copy, Clone)]
+pub enum NavigationCommand {
+    /// Move ahead by the distance.
+    Forward(usize),
+    /// Rise by the amount.
+    Up(usize),
+    /// Sink by the amount.
+    Down(usize),
+}
+
+#[derive(Copy, Clone)]
+enum NavigationStrategy {
+    Incorrect,
+    Correct
+}
+
+struct Position {
+    aim: usize,
+    horizontal: usize,
+    depth: usize,
+}
+
+fn parse(text: &str) -> Result<Vec<NavigationCommand>, Error> {
+    aoc_common::trimmed(text).into_iter().map(|command| {
+        let (direction, amount) = command.split_once(' ')
+            .ok_or(Error::InputParseError(Span::of(text, command)))?;
+        let amount = amount.parse().map_err(|_| Error::InputParseError(Span::of(text, amount)))?;
+        match direction {
+            "down"    => Ok(NavigationCommand::Down(amount)),
+            "up"      => Ok(NavigationCommand::Up(amount)),
+            "forward" => Ok(NavigationCommand::Forward(amount)),
+            _ => Err(Error::InputParseError(Span::of(text, direction)))
+        }
+    }).collect()
+}
+
+impl Position {
+
+    fn steer(&mut self, command: &NavigationCommand) {
+        match command {
+            NavigationCommand::Down(k) => { self.aim += k },
+            NavigationCommand::Up(k)   => { self.aim -= k },
+            NavigationCommand::Forward(k) => {
+                self.horizontal += k;
+                self.depth += self.aim * k;
+            }
+        }
+    }
+
+    fn steer_naive(&mut self, command: &NavigationCommand) {
+        match command {
+            NavigationCommand::Down(k)    => { self.depth += k },
+            NavigationCommand::Up(k)      => { self.depth -= k },
+            NavigationCommand::Forward(k) => { self.horizontal += k }
+        }
+    }
+
+    fn navigate(
+        &mut self,
+        strategy: NavigationStrategy,
+        commands: &Vec<NavigationCommand>
+    ) {
+        let mut steer = |cmd| { match strategy {
+            NavigationStrategy::Incorrect => self.steer_naive(cmd),
+            NavigationStrategy::Correct => self.steer(cmd)
+        }};
+        for command in commands { steer(command) }
+    }
+
+    fn new() -> Self {
+        Position { aim: 0, depth: 0, horizontal: 0 }
+    }
+}
+
+fn checksum(strategy: NavigationStrategy, commands: &Vec<NavigationCommand>) -> usize {
+    let mut position = Position::new();
+    position.navigate(strategy, commands);
+    position.depth * position.horizontal
+}
+
+/// Steers the submarine, never rising above the surface, and keeps track of
+/// where both readings of the commands take it.
+fn random_input(rng: &mut Rng) -> Generated {
+    let (mut text, mut position, mut aim, mut depth) = (String::new(), 0, 0, 0);
+    for _ in 0..rng.size(1..=200) {
+        let amount = rng.size(1..=9);
+        match rng.below(3) {
+            0 => {
+                text += &format!("forward {}\n", amount);
+                position += amount;
+                depth += aim * amount;
+            },
+            1 => {
+                text += &format!("down {}\n", amount);
+                aim += amount;
+            },
+            _ => {
+                let amount = amount.min(aim);
+                text += &format!("up {}\n", amount);
+                aim -= amount;
+            },
+        }
+    }
+    Generated::answered(text, position * aim, position * depth)
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day02;
+
+impl aoc_common::Solution for Day02 {
+    type Input = Vec<NavigationCommand>;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<NavigationCommand>, Error> {
+        parse(input)
+    }
+
+    fn part1(commands: &Vec<NavigationCommand>) -> Result<usize, Error> {
+        Ok(checksum(NavigationStrategy::Incorrect, commands))
+    }
+
+    fn part2(commands: &Vec<NavigationCommand>) -> Result<usize, Error> {
+        Ok(checksum(NavigationStrategy::Correct, commands))
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+}
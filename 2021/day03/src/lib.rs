@@ -0,0 +1,181 @@
+//! Advent of Code 2021, day 3: Binary Diagnostic.
+
+#![warn(missing_docs)]
+
+use std::fmt;
+
+use aoc_common::{Class, Generated, Rng, Span};
+
+/// Why the diagnostic report does not give a rating.
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// A character other than `0` or `1`.
+    InvalidDigit(char, Span),
+    /// A rate or rating has more bits than fit in a `usize`.
+    ConversionFailed,
+    /// The column at this index has neither a most nor a least common bit.
+    BalancedBitCount(usize),
+    /// Filtering left this many numbers rather than exactly one.
+    InvalidLeftover(usize),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidDigit(c, _) => write!(f, "Invalid binary digit: {}", c),
+            Error::ConversionFailed => write!(f, "Rating does not fit into a number."),
+            Error::BalancedBitCount(column) => write!(f, "Bit {} is 0 and 1 equally often.", column),
+            Error::InvalidLeftover(count) => write!(f, "{} entries left after filtering.", count),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::InvalidDigit(..) => Class::Parse,
+            Error::ConversionFailed => Class::Solve,
+            Error::BalancedBitCount(..) => Class::Solve,
+            Error::InvalidLeftover(..) => Class::Solve,
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::InvalidDigit(_, span) => Some(*span),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+enum LifeSupportDiagnostic {
+    OxygenGenertorRating,
+    CO2ScrubberRating,
+}
+
+fn life_support(
+    what: LifeSupportDiagnostic,
+    diagnostics: &[String]
+) -> Result<usize, Error> {
+    let mut workspace: Vec<&String> = diagnostics.iter().collect();
+    let (direction, default) = match what {
+        LifeSupportDiagnostic::OxygenGenertorRating => (1, '1'),
+        LifeSupportDiagnostic::CO2ScrubberRating => (-1, '0'),
+    };
+    'outer: for column in 0.. {
+        if workspace.len() <= 1 {
+            break;
+        }
+        let mut counter: isize = 0;
+        for entry in &workspace {
+            if let Some(digit) = entry.chars().nth(column) { 
+                counter += if digit == '1' {direction} else {-direction};
+            } else {
+                break 'outer;    
+            }
+        }
+        let bit = match counter {
+            t if t > 0 => '1',
+            t if t < 0 => '0',
+            _ => default
+        };
+        workspace.retain(|&entry| entry.chars().nth(column) == Some(bit));
+    }
+
+    match workspace.len() {
+        1 => usize::from_str_radix(workspace.first().unwrap(), 2)
+            .map_err(|_| Error::ConversionFailed),
+        t => Err(Error::InvalidLeftover(t))
+    }
+}
+
+fn performance(diagnostics: &[String]) -> Result<usize, Error> {
+    let mut gamma: usize = 0;
+    for column in 0.. {
+        let mut counter: isize = 0;
+        for entry in diagnostics {
+            if let Some(digit) = entry.chars().nth(column) { 
+                counter += if digit == '1' {1} else {-1};
+            } else {
+                let epsilon = !gamma & ((1 << column) - 1); 
+                return Ok(gamma * epsilon);
+            }
+        }
+        if counter == 0 {
+            return Err(Error::BalancedBitCount(column));
+        }
+        let bit = if counter > 0 {1} else {0};
+        gamma = (gamma << 1) | bit;
+    }
+    panic!("Control flow left infinite loop unexpectedly.");
+}
+
+
+/// The rating left after repeatedly keeping the numbers with the most (or
+/// least) common bit, preferring ones (or zeros) on ties.
+fn rating(mut numbers: Vec<usize>, width: usize, most: bool) -> Option<usize> {
+    for bit in (0..width).rev() {
+        if numbers.len() <= 1 {
+            break;
+        }
+        let ones = numbers.iter().filter(|&&n| n >> bit & 1 == 1).count();
+        let keep = (2 * ones >= numbers.len()) == most;
+        numbers.retain(|&n| (n >> bit & 1 == 1) == keep);
+    }
+    (numbers.len() == 1).then(|| numbers[0])
+}
+
+/// An odd number of distinct readings, so that no bit is balanced; inputs
+/// where a rating filters out every number are drawn again.
+fn random_input(rng: &mut Rng) -> Generated {
+    loop {
+        let width = rng.size(3..=12);
+        let mut numbers: Vec<usize> = (0..1 << width).collect();
+        rng.shuffle(&mut numbers);
+        numbers.truncate(2 * rng.size(0..=numbers.len().min(400) / 2 - 1) + 1);
+        let (Some(oxygen), Some(co2)) = (rating(numbers.clone(), width, true), rating(numbers.clone(), width, false)) else {
+            continue;
+        };
+        let gamma = (0..width).filter(|&bit| 2 * numbers.iter().filter(|&&n| n >> bit & 1 == 1).count() > numbers.len())
+            .fold(0, |gamma, bit| gamma | 1 << bit);
+        let epsilon = !gamma & ((1 << width) - 1);
+        let text = numbers.iter().map(|n| format!("{:0width$b}\n", n, width = width)).collect();
+        return Generated::answered(text, gamma * epsilon, oxygen * co2);
+    }
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day03;
+
+impl aoc_common::Solution for Day03 {
+    type Input = Vec<String>;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<String>, Error> {
+        let lines = aoc_common::trimmed(input);
+        for line in &lines {
+            if let Some(k) = line.find(|c| c != '0' && c != '1') {
+                let c = line[k..].chars().next().unwrap();
+                return Err(Error::InvalidDigit(c, Span::at(input, line, k)));
+            }
+        }
+        Ok(lines.into_iter().map(String::from).collect())
+    }
+
+    fn part1(lines: &Vec<String>) -> Result<usize, Error> {
+        performance(lines)
+    }
+
+    fn part2(lines: &Vec<String>) -> Result<usize, Error> {
+        let o2 = life_support(LifeSupportDiagnostic::OxygenGenertorRating, lines)?;
+        let co2 = life_support(LifeSupportDiagnostic::CO2ScrubberRating, lines)?;
+        Ok(o2 * co2)
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+}
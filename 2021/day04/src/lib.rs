@@ -0,0 +1,301 @@
+//! Advent of Code 2021, day 4: Giant Squid.
+
+#![warn(missing_docs)]
+
+use std::ops::Index;
+use regex::Regex;
+use std::fmt;
+use aoc_common::{Class, Generated, Grid, RenderFrame, Rng, Span};
+use aoc_common::animation::{self, Cell};
+
+/// Why the bingo game cannot be played.
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// There are no numbers to draw.
+    EmptyInput,
+    /// A board with this many rows is not as wide as it is high.
+    NotASquare(usize, Span),
+    /// Every number was drawn and no board won.
+    NoWinner,
+    /// A number too large to be drawn or written on a board.
+    InvalidNumber(Span),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::EmptyInput => write!(f, "Input is empty."),
+            Error::NotASquare(c, _) => write!(f, "Board was not a {c}×{c} square."),
+            Error::NoWinner => write!(f, "Noone won!"),
+            Error::InvalidNumber(_) => write!(f, "Number out of range."),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::EmptyInput => Class::Parse,
+            Error::NotASquare(..) => Class::Parse,
+            Error::NoWinner => Class::Solve,
+            Error::InvalidNumber(..) => Class::Parse,
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::NotASquare(_, span) => Some(*span),
+            Error::InvalidNumber(span) => Some(*span),
+            _ => None,
+        }
+    }
+}
+
+fn number(text: &str, digits: &str) -> Result<usize, Error> {
+    digits.parse().map_err(|_| Error::InvalidNumber(Span::of(text, digits)))
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Square(usize, bool);
+
+#[derive(Clone, Debug)]
+struct Board {
+    columns: usize,
+    squares: Vec<Square>,
+    won: bool,
+}
+
+/// The bingo boards with the numbers drawn for them.
+#[derive(Clone, Debug)]
+pub struct Game {
+    boards: Vec<Board>,
+    _input: Vec<usize>,
+    _round: usize,
+}
+
+impl Square {
+    fn new(value: usize) -> Self {
+        Square(value, false)
+    }
+    fn mark(&mut self, value: usize) {
+        if value == self.0 { self.1 = true; }
+    }
+    #[inline]
+    fn marked(&self) -> bool { self.1 }
+}
+
+impl From<&Square> for usize {
+    fn from(item: &Square) -> usize {item.0} 
+}
+
+impl Index<(usize, usize)> for Board {
+    type Output = Square;
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        let (row, col) = index;
+        if row >= self.columns || col >= self.columns { panic!() }
+        &self.squares[row * self.columns + col]
+    }
+}
+
+impl Board {
+    fn new(text: &str, grid: &str) -> Result<Self, Error> {
+        let number_pattern = Regex::new(r"\b\d+\b").unwrap();
+        let lines: Vec<&str> = grid.split('\n')
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .collect();
+        let columns = lines.len();
+        if columns == 0 {
+            return Err(Error::EmptyInput);
+        }
+        let grid: Vec<Vec<usize>> = lines.iter().map(|line|
+            number_pattern.find_iter(line).map(|nr| number(text, nr.as_str())).collect::<Result<Vec<_>,_>>()
+        ).collect::<Result<_,_>>()?;
+        if let Some(k) = grid.iter().position(|v| v.len() != columns) {
+            return Err(Error::NotASquare(columns, Span::of(text, lines[k])));
+        }
+        Ok(Board {
+            columns,
+            squares: grid.into_iter().flatten().map(Square::new).collect(),
+            won: false
+        })
+    }
+
+    fn winning(&mut self) -> bool {
+        if self.won {
+            false 
+        } else {
+            self.won = (0..self.columns).any(
+                |anchor| {
+                    (0..self.columns).map(|k| self[(anchor, k)]).all(|square| square.marked()) ||
+                    (0..self.columns).map(|k| self[(k, anchor)]).all(|square| square.marked())
+                }
+            );
+            self.won
+        }
+    }
+
+    fn play(&mut self, value: usize) -> &mut Self {
+        for square in &mut self.squares { square.mark(value) }
+        self
+    }
+}
+
+struct Win<'a> {
+    input: usize,
+    board: &'a Board
+}
+
+impl<'a> Win<'a> {
+    fn score(&'a self) -> usize {
+        self.input * self.board.squares.iter().fold(0, |a, x| if x.marked() {a} else {a + usize::from(x)})
+    }
+}
+
+
+impl Game {
+    fn new(input: &str) -> Result<Self, Error> {
+        let digits = Regex::new(r"\b\d+\b").unwrap();
+        let mut paragraphs = aoc_common::paragraphs(input).into_iter();
+        let input_values_string = paragraphs.next().ok_or(Error::EmptyInput)?;
+        Ok(Game {
+            _input: digits.find_iter(input_values_string).map(|nr| number(input, nr.as_str())).collect::<Result<_,_>>()?,
+            _round: 0,
+            boards: paragraphs.map(|grid| Board::new(input, grid)).collect::<Result<_,_>>()?
+        })
+    }
+
+    fn play_round(&mut self) -> Option<Win<'_>> {
+        let n = self.boards.len();
+        let m = self._input.len();
+        while self._round < m {
+            let input = self._input[self._round];
+            for i in 0..n {
+                if !self.boards[i].won {
+                    self.boards[i].play(input);
+                }
+            }
+            for i in 0..n {
+                if self.boards[i].winning() {
+                    return Some(Win{board: &self.boards[i], input});
+                }
+            }
+            self._round += 1;
+        }
+        None
+    }
+}
+
+impl Iterator for Game {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        self.play_round().map(|win| win.score())
+    }
+}
+
+/// Boards shown side by side in rows of this many.
+const BOARDS_PER_ROW: usize = 10;
+
+impl RenderFrame for Game {
+    /// Every board with its marked numbers in green, or yellow once it won.
+    fn frame(&self) -> Grid<Cell> {
+        let columns = self.boards.iter().map(|b| b.columns).max().unwrap_or(0);
+        let (across, down) = (3 * columns + 1, columns + 1);
+        let per_row = BOARDS_PER_ROW.min(self.boards.len()).max(1);
+        let rows = self.boards.len().div_ceil(per_row);
+        let mut frame = Grid::filled(across * per_row, down * rows, Cell::default());
+        for (k, board) in self.boards.iter().enumerate() {
+            let (left, top) = (k % per_row * across, k / per_row * down);
+            for (i, square) in board.squares.iter().enumerate() {
+                let colour = match (square.marked(), board.won) {
+                    (true, true) => animation::YELLOW,
+                    (true, false) => animation::GREEN,
+                    (false, _) => animation::grey(1, 2),
+                };
+                let (x, y) = (left + 3 * (i % board.columns), top + i / board.columns);
+                for (j, digit) in format!("{:>2}", usize::from(square) % 100).chars().enumerate() {
+                    frame[(x + j, y)] = Cell::coloured(digit, colour);
+                }
+            }
+        }
+        frame
+    }
+
+    /// Plays until the next board wins; stops when none is left to win.
+    fn step(&mut self) -> bool {
+        self.play_round().is_some()
+    }
+}
+
+/// Boards whose winning turn is the earliest turn by which some row or
+/// column is fully drawn; draws where the first or last winner is tied are
+/// made again.
+fn random_input(rng: &mut Rng) -> Generated {
+    const SIZE: usize = 5;
+    let boards: Vec<Vec<usize>> = (0..rng.size(2..=20)).map(|_| {
+        let mut numbers: Vec<usize> = (0..100).collect();
+        rng.shuffle(&mut numbers);
+        numbers[..SIZE * SIZE].to_vec()
+    }).collect();
+    loop {
+        let mut draws: Vec<usize> = (0..100).collect();
+        rng.shuffle(&mut draws);
+        let mut turn = [0; 100];
+        for (k, &n) in draws.iter().enumerate() {
+            turn[n] = k;
+        }
+        let wins: Vec<(usize, usize)> = boards.iter().map(|board| {
+            let line = |cells: &mut dyn Iterator<Item = usize>| cells.map(|n| turn[n]).max().unwrap();
+            let won = (0..SIZE).flat_map(|k| [
+                line(&mut (0..SIZE).map(|i| board[k * SIZE + i])),
+                line(&mut (0..SIZE).map(|i| board[i * SIZE + k])),
+            ]).min().unwrap();
+            let unmarked: usize = board.iter().filter(|&&n| turn[n] > won).sum();
+            (won, unmarked * draws[won])
+        }).collect();
+        let (first, last) = (wins.iter().min().unwrap(), wins.iter().max().unwrap());
+        if wins.iter().filter(|w| w.0 == first.0).count() > 1 || wins.iter().filter(|w| w.0 == last.0).count() > 1 {
+            continue;
+        }
+        let mut text = draws.iter().map(usize::to_string).collect::<Vec<_>>().join(",") + "\n";
+        for board in &boards {
+            text.push('\n');
+            for row in board.chunks(SIZE) {
+                text += &row.iter().map(|n| format!("{:>2}", n)).collect::<Vec<_>>().join(" ");
+                text.push('\n');
+            }
+        }
+        return Generated::answered(text, first.1, last.1);
+    }
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day04;
+
+impl aoc_common::Solution for Day04 {
+    type Input = Game;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Game, Error> {
+        Game::new(input)
+    }
+
+    fn part1(game: &Game) -> Result<usize, Error> {
+        game.clone().next().ok_or(Error::NoWinner)
+    }
+
+    fn part2(game: &Game) -> Result<usize, Error> {
+        game.clone().last().ok_or(Error::NoWinner)
+    }
+
+    fn animate(game: &Game) -> Option<Box<dyn RenderFrame>> {
+        Some(Box::new(game.clone()))
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+}
@@ -0,0 +1,299 @@
+//! Advent of Code 2021, day 5: Hydrothermal Venture.
+
+#![warn(missing_docs)]
+
+use num::rational::Rational64;
+use std::collections::HashSet;
+use std::collections::HashMap;
+use std::fmt;
+use aoc_common::{Class, Generated, Rng, Span};
+
+/// Why the hydrothermal vents cannot be read.
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// An end point that is not two coordinates.
+    PointParsingError(String, Span),
+    /// A line that is not two end points joined by `->`.
+    LineParsingError(String, Span),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::PointParsingError(spec, _) => write!(f, "Expected a point like `3,4`, got `{}`.", spec),
+            Error::LineParsingError(spec, _) => write!(f, "Expected a line like `0,9 -> 5,9`, got `{}`.", spec),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::PointParsingError(..) => Class::Parse,
+            Error::LineParsingError(..) => Class::Parse,
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::PointParsingError(_, span) => Some(*span),
+            Error::LineParsingError(_, span) => Some(*span),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+struct Point<T> {
+    x: T,
+    y: T,
+}
+
+impl<T> Point<T> where T: From<i64> {
+    fn new(text: &str, spec: &str) -> Result<Self, Error> {
+        let spec = spec.trim();
+        let parsed: Option<Vec<i64>> = spec.split(',').map(|x| x.parse().ok()).collect();
+        if let Some(entries) = parsed {
+            if entries.len() == 2 {
+                return Ok(Point {
+                    x: T::from(entries[0]),
+                    y: T::from(entries[1]),
+                })
+            }
+        }
+        Err(Error::PointParsingError(spec.to_string(), Span::of(text, spec)))
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+enum Line {
+    Sloped(Rational64, Rational64),
+    Vertical(i64)
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+struct LineSegment(Point<i64>, Point<i64>);
+
+struct LineSegmentIterator<T>
+{
+    line: Line,
+    point: Option<Point<T>>,
+    end: Point<T>,
+}
+
+impl From<&LineSegment> for Line {
+    fn from(segment: &LineSegment) -> Line {
+        let LineSegment(p, q) = segment;
+        match p.x - q.x {
+            0 => Line::Vertical(p.x),
+            d => {
+                let a = Rational64::new(p.y - q.y, d);
+                let b = Rational64::new(p.x * q.y - p.y * q.x, d);
+                Line::Sloped(a, b)
+            }
+        }
+    }
+}
+
+impl<T> std::fmt::Display for Point<T>
+    where T: std::fmt::Display
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl std::fmt::Display for Line {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Line::Vertical(x) => write!(f, "<x = {}>", x),
+            Line::Sloped(a,b) => if *a.numer() == 0 {
+                write!(f, "<x = {}>", b)
+            } else {
+                write!(f, "<y = {} * x + {}>", a, b)
+            }
+        }
+    }
+}
+
+impl LineSegment {
+    fn new(text: &str, spec: &str) -> Result<Self, Error> {
+        let points: Vec<&str> = spec.split("->").collect();
+        if points.len() != 2 {
+            Err(Error::LineParsingError(spec.to_string(), Span::of(text, spec)))
+        } else {
+            let p = Point::new(text, points[0])?;
+            let q = Point::new(text, points[1])?;
+            let mut segment = LineSegment(p, q);
+            if match Line::from(&segment) {
+                Line::Vertical(_) => p.y > q.y,
+                Line::Sloped(_,_) => p.x > q.x,
+            } {
+                segment = LineSegment(q, p);
+            }
+            Ok(segment)
+        }
+    }
+
+    fn iter(&self) -> LineSegmentIterator<i64> {
+        LineSegmentIterator {
+            line: Line::from(self),
+            point: Some(self.0),
+            end: self.1
+        }
+    }
+}
+
+impl Iterator for LineSegmentIterator<i64>
+{
+    type Item = Point<i64>;
+
+    fn next(&mut self) -> Option<Point<i64>> {
+        let q = self.end;
+        let p = self.point?;
+        self.point = match self.line {
+            Line::Vertical(x) => {
+                if p.y < q.y { Some(Point{x, y: p.y + 1}) } else { None }
+            },
+            Line::Sloped(a,b) => {
+                let mut next = None;
+                for x in p.x+1..=q.x {
+                    let y = a * x + b;
+                    if y.is_integer() {
+                        next = Some(Point{x, y: y.to_integer()});
+                        break;
+                    }
+                }
+                next
+            }
+        };
+        Some(p)
+    }
+}
+
+impl Line {
+    fn is_on_grid(&self) -> bool {
+        match self {
+            Line::Vertical(_) => true,
+            Line::Sloped(a,_) => *a.numer() == 0
+        }
+    }
+}
+
+/// The lines of hydrothermal vents.
+#[derive(Clone)]
+pub struct OceanFloor {
+    clouds: HashSet<LineSegment>
+}
+
+impl OceanFloor {
+    fn new(text: &str) -> Result<Self, Error> {
+        Ok(OceanFloor{ clouds: aoc_common::trimmed(text)
+            .into_iter()
+            .map(|x| LineSegment::new(text, x))
+            .collect::<Result<HashSet<_>, Error>>()?
+        })
+    }
+
+    fn restrict_to_grid(mut self) -> Self {
+        self.clouds.retain(|line| {
+            Line::from(line).is_on_grid()
+        });
+        self
+    }
+
+    fn count_hotspots(&self, minimum: usize) -> usize {
+        let mut coverage: HashMap<Point<i64>, usize> = HashMap::new();
+        for cloud in self.clouds.iter() {
+            for point in cloud.iter() {
+                coverage.insert(point, coverage.get(&point).copied().unwrap_or(0) + 1);
+            }
+        }
+        coverage.into_values().filter(|&t| t >= minimum).count()
+    }
+
+}
+
+/// The vents moved as close to the origin as they fit, or scaled by half, or
+/// with one line cut to half its length; none if a line does not read.
+fn simpler_vents(text: &str) -> Vec<String> {
+    let read = |line: &str| -> Option<[i64; 4]> {
+        let (p, q) = line.split_once("->")?;
+        let (x1, y1) = p.trim().split_once(',')?;
+        let (x2, y2) = q.trim().split_once(',')?;
+        Some([x1.parse().ok()?, y1.parse().ok()?, x2.parse().ok()?, y2.parse().ok()?])
+    };
+    let Some(lines) = aoc_common::trimmed(text).into_iter().map(read).collect::<Option<Vec<_>>>() else {
+        return Vec::new();
+    };
+    let write = |lines: &[[i64; 4]]| -> String {
+        lines.iter().map(|[x1, y1, x2, y2]| format!("{},{} -> {},{}\n", x1, y1, x2, y2)).collect()
+    };
+    let mut result = Vec::new();
+    let low_x = lines.iter().flat_map(|l| [l[0], l[2]]).min().unwrap_or(0).max(0);
+    let low_y = lines.iter().flat_map(|l| [l[1], l[3]]).min().unwrap_or(0).max(0);
+    if low_x > 0 || low_y > 0 {
+        result.push(write(&lines.iter().map(|&[x1, y1, x2, y2]| [x1 - low_x, y1 - low_y, x2 - low_x, y2 - low_y]).collect::<Vec<_>>()));
+    }
+    if lines.iter().flatten().any(|&n| n != 0) {
+        result.push(write(&lines.iter().map(|line| line.map(|n| n / 2)).collect::<Vec<_>>()));
+    }
+    for k in 0..lines.len() {
+        let [x1, y1, x2, y2] = lines[k];
+        let mut cut = lines.clone();
+        cut[k] = [x1, y1, x1 + (x2 - x1) / 2, y1 + (y2 - y1) / 2];
+        if cut[k] != lines[k] {
+            result.push(write(&cut));
+        }
+    }
+    result
+}
+
+/// Horizontal, vertical and diagonal lines of vents on a 100×100 floor.
+fn random_input(rng: &mut Rng) -> Generated {
+    let mut text = String::new();
+    for _ in 0..rng.size(1..=100) {
+        let (x, y) = (rng.range(0..=99), rng.range(0..=99));
+        let (dx, dy) = *rng.pick(&[(1, 0), (0, 1), (1, 1), (1, -1)]);
+        let length = rng.range(1..=40);
+        let (x2, y2) = (x + dx * length, y + dy * length);
+        if !(0..=99).contains(&x2) || !(0..=99).contains(&y2) {
+            continue;
+        }
+        if rng.chance(0.5) {
+            text += &format!("{},{} -> {},{}\n", x, y, x2, y2);
+        } else {
+            text += &format!("{},{} -> {},{}\n", x2, y2, x, y);
+        }
+    }
+    Generated::text(text)
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day05;
+
+impl aoc_common::Solution for Day05 {
+    type Input = OceanFloor;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<OceanFloor, Error> {
+        OceanFloor::new(input)
+    }
+
+    fn part1(floor: &OceanFloor) -> Result<usize, Error> {
+        Ok(floor.clone().restrict_to_grid().count_hotspots(2))
+    }
+
+    fn part2(floor: &OceanFloor) -> Result<usize, Error> {
+        Ok(floor.count_hotspots(2))
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+
+    fn simplify(text: &str) -> Vec<String> {
+        simpler_vents(text)
+    }
+}
@@ -0,0 +1,132 @@
+//! Advent of Code 2021, day 6: Lanternfish.
+
+#![warn(missing_docs)]
+
+use std::fmt;
+
+use aoc_common::{Class, Generated, References, Rng, Span};
+
+/// Why the lanternfish cannot be counted.
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// A timer that is not a number.
+    ParsingError(String, Span),
+    /// A timer longer than a newborn fish's.
+    InvalidTurn(usize, Span),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::ParsingError(t, _) => write!(f, "Invalid timer value: `{}`", t),
+            Error::InvalidTurn(turn, _) => write!(f, "Timer {} exceeds the spawn timer of {}.", turn, SPWAN_TURN),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::ParsingError(..) => Class::Parse,
+            Error::InvalidTurn(..) => Class::Parse,
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::ParsingError(_, span) => Some(*span),
+            Error::InvalidTurn(_, span) => Some(*span),
+        }
+    }
+}
+
+const SPWAN_TURN: usize = 8;
+const RESET_TURN: usize = 6;
+
+/// The number of fish with each timer value.
+pub type Swarm = [usize; SPWAN_TURN + 1];
+
+fn read_fishes(spec: &str) -> Result<Swarm,Error> {
+    let mut counts: Swarm = [0; SPWAN_TURN + 1];
+    for t in spec.split(',').map(str::trim) {
+        let turn = t.parse::<usize>().map_err(|_| Error::ParsingError(t.to_string(), Span::of(spec, t)))?;
+        if turn > SPWAN_TURN {
+            return Err(Error::InvalidTurn(turn, Span::of(spec, t)));
+        }
+        counts[turn] += 1;
+    }
+    Ok(counts)
+}
+
+fn age(swarm: &mut Swarm) {
+    let spawns = swarm[0];
+    swarm.rotate_left(1);
+    swarm[SPWAN_TURN]  = spawns;
+    swarm[RESET_TURN] += spawns;
+}
+
+fn population(swarm: &Swarm, days: usize) -> usize {
+    let mut swarm = *swarm;
+    for _ in 0..days { age(&mut swarm); }
+    swarm.iter().sum()
+}
+
+/// The population after `days`, ageing every fish on its own.
+fn population_of_fishes(swarm: &Swarm, days: usize) -> usize {
+    let mut fishes: Vec<usize> = (0..=SPWAN_TURN).flat_map(|turn| std::iter::repeat_n(turn, swarm[turn])).collect();
+    for _ in 0..days {
+        let spawns = fishes.iter().filter(|&&turn| turn == 0).count();
+        for turn in fishes.iter_mut() {
+            *turn = if *turn == 0 { RESET_TURN } else { *turn - 1 };
+        }
+        fishes.extend(std::iter::repeat_n(SPWAN_TURN, spawns));
+    }
+    fishes.len()
+}
+
+/// The population after `days`, counting the descendants of every fish;
+/// too many fish are born by day 256 to age them one by one.
+fn population_by_descendants(swarm: &Swarm, days: usize) -> usize {
+    // descendants[n]: the fish a fish about to spawn grows into, itself
+    // included, when `n` days are left after its timer runs out.
+    let mut descendants = vec![1; days + 1];
+    for n in 1..=days {
+        descendants[n] = descendants[n.saturating_sub(RESET_TURN + 1)] + descendants[n.saturating_sub(SPWAN_TURN + 1)];
+    }
+    (0..=SPWAN_TURN).map(|turn| swarm[turn] * days.checked_sub(turn).map_or(1, |n| descendants[n])).sum()
+}
+
+fn random_input(rng: &mut Rng) -> Generated {
+    let timers: Vec<String> = (0..rng.size(1..=300)).map(|_| rng.range(1..=5).to_string()).collect();
+    Generated::text(timers.join(",") + "\n")
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day06;
+
+impl aoc_common::Solution for Day06 {
+    type Input = Swarm;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Swarm, Error> {
+        read_fishes(input)
+    }
+
+    fn part1(swarm: &Swarm) -> Result<usize, Error> {
+        Ok(population(swarm, 80))
+    }
+
+    fn part2(swarm: &Swarm) -> Result<usize, Error> {
+        Ok(population(swarm, 256))
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+
+    fn reference(swarm: &Swarm) -> Option<References> {
+        Some([Some(population_of_fishes(swarm, 80).into()), Some(population_by_descendants(swarm, 256).into())])
+    }
+}
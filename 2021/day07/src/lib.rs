@@ -0,0 +1,96 @@
+//! Advent of Code 2021, day 7: The Treachery of Whales.
+
+#![warn(missing_docs)]
+
+use std::collections::HashMap;
+use std::fmt;
+use aoc_common::{Class, Generated, Rng, Span};
+
+/// Why the crabs cannot be aligned.
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// A position that is not a number.
+    ParsingError(Span),
+    /// There is nothing to align.
+    NoCrabs,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::ParsingError(_) => write!(f, "Expected comma-separated crab positions."),
+            Error::NoCrabs => write!(f, "There are no crabs."),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::ParsingError(..) => Class::Parse,
+            Error::NoCrabs => Class::Solve,
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::ParsingError(span) => Some(*span),
+            _ => None,
+        }
+    }
+}
+
+/// The number of crabs at each horizontal position.
+pub struct Crabs(HashMap<isize, isize>);
+
+impl Crabs {
+    fn new(data: &str) -> Result<Self, Error> {
+        let mut result = HashMap::new();
+        for t in data.split(',').map(str::trim) {
+            let fuel = t.parse::<isize>().map_err(|_| Error::ParsingError(Span::of(data, t)))?;
+            result.insert(fuel, result.get(&fuel).unwrap_or(&0) + 1);
+        }
+        Ok(Crabs(result))
+    }
+
+    fn fuel_cost(&self, to: isize, computation: fn(isize) -> isize) -> isize {
+        self.0.iter().map(|(&position, count)| computation((position - to).abs()) * count).sum()
+    }
+
+    fn minimum_fuel_cost(&self, computation: fn(isize) -> isize) -> Option<isize> {
+        let lower_bound = *self.0.keys().min()?;
+        let upper_bound = *self.0.keys().max()?;
+        (lower_bound..=upper_bound).map(|to| self.fuel_cost(to, computation)).min()
+    }
+}
+
+fn random_input(rng: &mut Rng) -> Generated {
+    let positions: Vec<String> = (0..rng.size(1..=300)).map(|_| rng.range(0..=1000).to_string()).collect();
+    Generated::text(positions.join(",") + "\n")
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day07;
+
+impl aoc_common::Solution for Day07 {
+    type Input = Crabs;
+    type Error = Error;
+    type Part1 = isize;
+    type Part2 = isize;
+
+    fn parse(input: &str) -> Result<Crabs, Error> {
+        Crabs::new(input)
+    }
+
+    fn part1(crabs: &Crabs) -> Result<isize, Error> {
+        crabs.minimum_fuel_cost(|t| t).ok_or(Error::NoCrabs)
+    }
+
+    fn part2(crabs: &Crabs) -> Result<isize, Error> {
+        crabs.minimum_fuel_cost(|t| t * (t+1) / 2).ok_or(Error::NoCrabs)
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+}
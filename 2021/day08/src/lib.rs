@@ -0,0 +1,260 @@
+//! Advent of Code 2021, day 8: Seven Segment Search.
+
+#![warn(missing_docs)]
+
+use std::cmp::Eq;
+use std::convert::TryInto;
+use std::collections::HashSet;
+use regex::Regex;
+use itertools::Itertools;
+use std::fmt;
+use aoc_common::{Class, Generated, Rng, Span};
+
+/// Why the seven-segment displays cannot be fixed.
+#[derive(Clone, Debug)]
+pub enum Error {
+    /// A line that is not ten patterns, `|` and four digits.
+    InvalidFormat(String, Span),
+    /// A segment other than `a` to `g`.
+    InvalidWire(char, Span),
+    /// No assignment of wires to segments fits the ten patterns.
+    CouldNotRewire,
+    /// An output digit that is not a digit even once rewired.
+    WiringStillBroken
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidFormat(line, _) => write!(f, "Expected ten signals, `|` and four digits: `{}`", line),
+            Error::InvalidWire(c, _) => write!(f, "Invalid wire: {}", c),
+            Error::CouldNotRewire => write!(f, "No wiring explains the signals."),
+            Error::WiringStillBroken => write!(f, "Rewired signal is not a digit."),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::InvalidFormat(..) => Class::Parse,
+            Error::InvalidWire(..) => Class::Parse,
+            Error::CouldNotRewire => Class::Solve,
+            Error::WiringStillBroken => Class::Solve,
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::InvalidFormat(_, span) => Some(*span),
+            Error::InvalidWire(_, span) => Some(*span),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum Wire {
+    A, B, C, D, E, F, G
+}
+
+impl From<Wire> for usize {
+    #[inline]
+    fn from(t: Wire) -> usize { t as usize }
+}
+
+#[derive(Clone, Copy, PartialOrd, Ord, PartialEq, Eq, Hash)]
+struct Signal(u8);
+
+type Wiring = [Wire; 7];
+
+struct Signals(Vec<Signal>);
+
+struct SignalIterator {
+    signal: u8,
+    offset: u8
+}
+
+impl Iterator for SignalIterator {
+    type Item = Wire;
+
+    fn next(&mut self) -> Option<Wire> {
+        let mut offset = self.offset;
+        while offset <= 6 {
+            self.offset += 1;
+            if (self.signal >> offset) & 1 == 1 {
+                break;
+            }
+            offset = self.offset;
+        }
+        match offset {
+            0 => Some(Wire::A),
+            1 => Some(Wire::B),
+            2 => Some(Wire::C),
+            3 => Some(Wire::D),
+            4 => Some(Wire::E),
+            5 => Some(Wire::F),
+            6 => Some(Wire::G),
+            _ => None
+        }        
+    }
+}
+
+impl Signal {
+    fn new(wires: &HashSet<Wire>) -> Self {
+        Signal(wires.iter().fold(0, |a, &w| a | (1 << usize::from(w))))
+    }
+
+    fn iter(&self) -> SignalIterator {
+        let &Signal(s) = self;
+        SignalIterator { signal: s, offset: 0 }
+    }
+
+    fn rewire(&self, wiring: Wiring) -> Self {
+        Signal::new(&self.iter().map(|w| wiring[usize::from(w)]).collect())
+    }
+
+    fn display(&self) -> Result<usize, Error> {
+        SIGNAL_DEFAULTS
+            .into_iter()
+            .enumerate()
+            .filter_map(|(k, s)| if s == *self { Some(k) } else { None })
+            .next()
+            .ok_or(Error::WiringStillBroken)
+    }
+}
+
+impl Signals {
+    fn new(text: &str, signals: &str) -> Result<Self, Error> {
+        let space = Regex::new(r"\s+").unwrap();
+        let signals: Vec<HashSet<Wire>> = space.split(signals.trim())
+            .map(|signal| signal.char_indices().map(|(k, c)| match c {
+                'a' => Ok(Wire::A),
+                'b' => Ok(Wire::B),
+                'c' => Ok(Wire::C),
+                'd' => Ok(Wire::D),
+                'e' => Ok(Wire::E),
+                'f' => Ok(Wire::F),
+                'g' => Ok(Wire::G),
+                _  => Err(Error::InvalidWire(c, Span::at(text, signal, k)))
+            }).collect::<Result<_,_>>()).collect::<Result<_,_>>()?;
+        Ok(Signals(signals.iter().map(Signal::new).collect()))
+    }
+}
+
+/// The ten signal patterns of one display and the four digits it shows.
+pub struct BrokenScreen {
+    signals: [Signal; 0xA],
+    display: [Signal; 0x4]
+}
+
+const SIGNAL_DEFAULTS: [Signal;10] = [
+    Signal(0b1110111),
+    Signal(0b0100100),
+    Signal(0b1011101),
+    Signal(0b1101101),
+    Signal(0b0101110),
+    Signal(0b1101011),
+    Signal(0b1111011),
+    Signal(0b0100101),
+    Signal(0b1111111),
+    Signal(0b1101111),
+];
+
+
+impl BrokenScreen {
+    fn new(text: &str, encoded: &str) -> Result<Self, Error> {
+        let err = || Error::InvalidFormat(encoded.to_string(), Span::of(text, encoded));
+        let mut parts = encoded.split('|');
+        let mut read = || {Signals::new(text, parts.next().ok_or(err())?)};
+        let Signals(signals) = read()?;
+        let Signals(display) = read()?;
+        Ok(BrokenScreen {
+            signals: signals.try_into().map_err(|_| err())?,
+            display: display.try_into().map_err(|_| err())?,
+        })
+    }
+
+    fn is_valid(&self, wiring: Wiring) -> bool {
+        let mut converted: HashSet<Signal> = self.signals.iter()
+            .map(|signal| signal.rewire(wiring))
+            .collect();
+        for signal in &SIGNAL_DEFAULTS {
+            converted.remove(signal);
+        }
+        converted.is_empty()
+    }
+
+    fn fix(&self) -> Option<Wiring> {
+        let all = [Wire::A,Wire::B,Wire::C,Wire::D,Wire::E,Wire::F,Wire::G];
+        for permutation in all.into_iter().permutations(7) {
+            let wiring: Wiring = permutation.try_into().unwrap();
+            if self.is_valid(wiring) { return Some(wiring); }
+        }
+        None
+    }
+}
+
+fn digits(screen: &BrokenScreen) -> Result<Vec<usize>, Error> {
+    let wiring = screen.fix().ok_or(Error::CouldNotRewire)?;
+    screen.display.iter().map(|t| t.rewire(wiring).display()).collect()
+}
+
+/// Screens rewired at random, showing four random digits.
+fn random_input(rng: &mut Rng) -> Generated {
+    const DIGITS: [&str; 10] = ["abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg"];
+    let (mut text, mut easy, mut sum) = (String::new(), 0, 0);
+    for _ in 0..rng.size(1..=40) {
+        let mut wires: Vec<char> = ('a'..='g').collect();
+        rng.shuffle(&mut wires);
+        let scramble = |rng: &mut Rng, digit: usize| {
+            let mut signal: Vec<char> = DIGITS[digit].chars().map(|c| wires[c as usize - 'a' as usize]).collect();
+            rng.shuffle(&mut signal);
+            signal.into_iter().collect::<String>()
+        };
+        let mut digits: Vec<usize> = (0..10).collect();
+        rng.shuffle(&mut digits);
+        let signals: Vec<String> = digits.iter().map(|&d| scramble(rng, d)).collect();
+        let shown: Vec<usize> = (0..4).map(|_| rng.size(0..=9)).collect();
+        let display: Vec<String> = shown.iter().map(|&d| scramble(rng, d)).collect();
+        text += &format!("{} | {}\n", signals.join(" "), display.join(" "));
+        easy += shown.iter().filter(|d| [1, 4, 7, 8].contains(d)).count();
+        sum += shown.iter().fold(0, |n, d| 10 * n + d);
+    }
+    Generated::answered(text, easy, sum)
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day08;
+
+impl aoc_common::Solution for Day08 {
+    type Input = Vec<BrokenScreen>;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<BrokenScreen>, Error> {
+        aoc_common::trimmed(input).iter().map(|line| BrokenScreen::new(input, line)).collect()
+    }
+
+    fn part1(screens: &Vec<BrokenScreen>) -> Result<usize, Error> {
+        let mut sum = 0;
+        for screen in screens {
+            sum += digits(screen)?.into_iter()
+                .filter(|&t| t == 1 || t == 4 || t == 7 || t == 8).count();
+        }
+        Ok(sum)
+    }
+
+    fn part2(screens: &Vec<BrokenScreen>) -> Result<usize, Error> {
+        let mut sum = 0;
+        for screen in screens {
+            sum += digits(screen)?.into_iter().fold(0, |a, d| a * 10 + d);
+        }
+        Ok(sum)
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+}
@@ -0,0 +1,129 @@
+//! Advent of Code 2021, day 9: Smoke Basin.
+
+#![warn(missing_docs)]
+
+use std::fmt;
+use aoc_common::{Class, Generated, Grid, GridError, Image, Rng, Search, Span};
+use aoc_common::grid::{Point, ORTHOGONAL};
+
+/// Why the height map cannot be read.
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// A height that is not a digit.
+    InvalidCharacter(char, Span),
+    /// A row that is shorter or longer than the first.
+    RaggedRow(Span),
+    /// The map has no rows.
+    EmptyMap,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidCharacter(c, _) => write!(f, "Invalid height: {}", c),
+            Error::RaggedRow(_) => write!(f, "Map is not rectangular."),
+            Error::EmptyMap => write!(f, "Map is empty."),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::InvalidCharacter(..) => Class::Parse,
+            Error::RaggedRow(..) => Class::Parse,
+            Error::EmptyMap => Class::Parse,
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::InvalidCharacter(_, span) => Some(*span),
+            Error::RaggedRow(span) => Some(*span),
+            _ => None,
+        }
+    }
+}
+
+impl From<GridError> for Error {
+    fn from(error: GridError) -> Self {
+        match error {
+            GridError::InvalidCharacter(c, span) => Error::InvalidCharacter(c, span),
+            GridError::RaggedRow(span) => Error::RaggedRow(span),
+            GridError::Empty => Error::EmptyMap,
+        }
+    }
+}
+
+/// The height of every location.
+pub struct Map(Grid<usize>);
+
+impl Map {
+    fn parse(text: &str) -> Result<Self,Error> {
+        Ok(Map(Grid::parse(text, |c| c.to_digit(10).map(|d| d as usize))?))
+    }
+
+    #[inline]
+    fn risk(&self, spot: Point) -> usize {
+        self.0[spot] + 1
+    }
+
+    fn lows(&self) -> impl Iterator<Item = Point> + '_ {
+        let Map(grid) = self;
+        grid.points().filter(move |&spot| {
+            grid.neighbours(spot, &ORTHOGONAL).all(|n| grid[spot] < grid[n])
+        })
+    }
+
+    fn basin(&self, center: Point) -> usize {
+        let Map(grid) = self;
+        Search::new(center, |&spot: &Point| grid
+                .neighbours(spot, &ORTHOGONAL)
+                .filter(|&n| grid[n] < 9)
+                .map(|n| (n, 1))
+            )
+            .breadth_first()
+            .visited(grid.map(|_| None))
+            .count()
+    }
+}
+
+fn random_input(rng: &mut Rng) -> Generated {
+    let width = rng.size(1..=40);
+    let rows: Vec<String> = (0..rng.size(1..=40))
+        .map(|_| (0..width).map(|_| char::from(b'0' + rng.below(10) as u8)).collect::<String>() + "\n")
+        .collect();
+    Generated::text(rows.concat())
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day09;
+
+impl aoc_common::Solution for Day09 {
+    type Input = Map;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Map, Error> {
+        Map::parse(input)
+    }
+
+    fn part1(map: &Map) -> Result<usize, Error> {
+        Ok(map.lows().map(|s| map.risk(s)).sum())
+    }
+
+    fn part2(map: &Map) -> Result<usize, Error> {
+        let mut basins: Vec<usize> = map.lows().map(|spot| map.basin(spot)).collect();
+        basins.sort_by(|a, b| b.cmp(a));
+        Ok(basins.iter().take(3).product())
+    }
+
+    fn render(map: &Map) -> Result<Option<Image>, Error> {
+        Ok(Some(Image::graymap(&map.0, |&height| height as u16)))
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+}
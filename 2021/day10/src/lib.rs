@@ -0,0 +1,186 @@
+//! Advent of Code 2021, day 10: Syntax Scoring.
+
+#![warn(missing_docs)]
+
+use std::cmp::{Eq, PartialEq};
+use std::fmt;
+use aoc_common::{Class, Generated, Rng};
+
+/// Why the navigation subsystem cannot be scored.
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// Every line is either complete or corrupted, so there is no middle score.
+    NoIncompleteLines,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::NoIncompleteLines => write!(f, "No line is incomplete."),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::NoIncompleteLines => Class::Solve,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+enum ChunkType {
+    Round = 1,
+    Square = 2,
+    Curly = 3,
+    Pointy = 4,
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+enum Chunk { Open(ChunkType), Close(ChunkType) }
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+enum SyntaxCheckResult {
+    Corrupt(ChunkType),
+    Incomplete(Vec<ChunkType>),
+    Valid
+}
+
+struct Line(Vec<Chunk>);
+
+/// The lines of the navigation subsystem.
+pub struct Subsystem {
+    lines: Vec<Line>
+}
+
+impl SyntaxCheckResult {
+    fn score_errors(&self) -> usize {
+        match self {
+            SyntaxCheckResult::Corrupt(ChunkType::Round)  => 3,
+            SyntaxCheckResult::Corrupt(ChunkType::Square) => 57,
+            SyntaxCheckResult::Corrupt(ChunkType::Curly)  => 1197,
+            SyntaxCheckResult::Corrupt(ChunkType::Pointy) => 25137,
+            _ => 0
+        }
+    }
+    fn score_syntax(&self) -> usize {
+        if let SyntaxCheckResult::Incomplete(v) = self {
+            v.iter().fold(0, |score, &t| score * 5 + (t as usize))
+        } else { 0 }
+    }
+}
+
+impl Line {
+    fn new(input: &str) -> Self {
+        Line(input.chars().filter_map(|c| match c {
+            '(' => Some(Chunk::Open(ChunkType::Round)),
+            '[' => Some(Chunk::Open(ChunkType::Square)),
+            '{' => Some(Chunk::Open(ChunkType::Curly)),
+            '<' => Some(Chunk::Open(ChunkType::Pointy)),
+            ')' => Some(Chunk::Close(ChunkType::Round)),
+            ']' => Some(Chunk::Close(ChunkType::Square)),
+            '}' => Some(Chunk::Close(ChunkType::Curly)),
+            '>' => Some(Chunk::Close(ChunkType::Pointy)),
+             _  => None
+        }).collect())
+    }
+
+    fn check(&self) -> SyntaxCheckResult {
+        let mut stack: Vec<ChunkType> = Vec::new();
+        let Line(chunks) = self;
+        for chunk in chunks.iter().copied() {
+            match chunk {
+                Chunk::Open(t) => {
+                    stack.push(t);
+                }
+                Chunk::Close(t) => {
+                    if stack.pop() != Some(t) {
+                        return SyntaxCheckResult::Corrupt(t)
+                    }
+                }
+            }
+        }
+        if stack.is_empty() {
+            SyntaxCheckResult::Valid
+        } else {
+            stack.reverse();
+            SyntaxCheckResult::Incomplete(stack)
+        }
+    }
+}
+
+impl Subsystem {
+    fn parse(text: &str) -> Self {
+        let mut lines: Vec<Line> = Vec::new();
+        for line in aoc_common::trimmed(text) {
+            lines.push(Line::new(line));
+        }
+        Subsystem{lines}
+    }
+}
+
+/// Lines that are either cut short or broken by a wrong closing character,
+/// with an odd number of the former.
+fn random_input(rng: &mut Rng) -> Generated {
+    const OPEN: [char; 4] = ['(', '[', '{', '<'];
+    const CLOSE: [char; 4] = [')', ']', '}', '>'];
+    const ERROR: [usize; 4] = [3, 57, 1197, 25137];
+    let incomplete = 2 * rng.size(0..=20) + 1;
+    let corrupt = rng.size(0..=40);
+    let (mut lines, mut errors, mut completions) = (Vec::new(), 0, Vec::new());
+    for k in 0..incomplete + corrupt {
+        let (mut line, mut stack) = (String::new(), Vec::new());
+        let length = rng.size(1..=80);
+        while line.len() < length || stack.is_empty() {
+            if stack.is_empty() || (stack.len() < 20 && rng.chance(0.55)) {
+                let kind = rng.size(0..=3);
+                line.push(OPEN[kind]);
+                stack.push(kind);
+            } else {
+                line.push(CLOSE[stack.pop().unwrap()]);
+            }
+        }
+        if k < incomplete {
+            completions.push(stack.iter().rev().fold(0, |score, kind| 5 * score + kind + 1));
+        } else {
+            let wrong = (stack.last().unwrap() + rng.size(1..=3)) % 4;
+            line.push(CLOSE[wrong]);
+            errors += ERROR[wrong];
+            line.extend((0..rng.size(0..=10)).map(|_| *rng.pick(&OPEN)));
+        }
+        lines.push(line + "\n");
+    }
+    rng.shuffle(&mut lines);
+    completions.sort();
+    Generated::answered(lines.concat(), errors, completions[completions.len() / 2])
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day10;
+
+impl aoc_common::Solution for Day10 {
+    type Input = Subsystem;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Subsystem, Error> {
+        Ok(Subsystem::parse(input))
+    }
+
+    fn part1(subsystem: &Subsystem) -> Result<usize, Error> {
+        Ok(subsystem.lines.iter().map(|l| l.check().score_errors()).sum())
+    }
+
+    fn part2(subsystem: &Subsystem) -> Result<usize, Error> {
+        let mut scores: Vec<_> = subsystem.lines.iter()
+            .map(|l| l.check().score_syntax()).filter(|&t| t > 0).collect();
+        scores.sort();
+        scores.get(scores.len() / 2).copied().ok_or(Error::NoIncompleteLines)
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+}
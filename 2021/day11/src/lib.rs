@@ -0,0 +1,182 @@
+//! Advent of Code 2021, day 11: Dumbo Octopus.
+
+#![warn(missing_docs)]
+
+use std::collections::HashSet;
+use std::cmp::{Eq, PartialEq};
+use std::fmt;
+use aoc_common::{Class, Generated, Grid, GridError, Image, RenderFrame, Rng, Span};
+use aoc_common::animation::{self, Cell};
+use aoc_common::grid::{Point, SURROUNDING};
+
+/// Why the octopus grid cannot be read.
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// An energy level that is not a digit.
+    InvalidCharacter(char, Span),
+    /// A row that is shorter or longer than the first.
+    RaggedRow(Span),
+    /// The grid has no rows.
+    EmptyMap,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidCharacter(c, _) => write!(f, "Invalid energy level: {}", c),
+            Error::RaggedRow(_) => write!(f, "Map is not rectangular."),
+            Error::EmptyMap => write!(f, "Map is empty."),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::InvalidCharacter(..) => Class::Parse,
+            Error::RaggedRow(..) => Class::Parse,
+            Error::EmptyMap => Class::Parse,
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::InvalidCharacter(_, span) => Some(*span),
+            Error::RaggedRow(span) => Some(*span),
+            _ => None,
+        }
+    }
+}
+
+impl From<GridError> for Error {
+    fn from(error: GridError) -> Self {
+        match error {
+            GridError::InvalidCharacter(c, span) => Error::InvalidCharacter(c, span),
+            GridError::RaggedRow(span) => Error::RaggedRow(span),
+            GridError::Empty => Error::EmptyMap,
+        }
+    }
+}
+
+const MAX_ENERGY: usize = 9;
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+struct Octopus(usize);
+
+/// The energy level of every octopus.
+#[derive(Clone)]
+pub struct Map(Grid<Octopus>);
+
+impl Map {
+    fn parse(text: &str) -> Result<Self,Error> {
+        Ok(Map(Grid::parse(text, |c| c.to_digit(10).map(|d| Octopus(d as usize)))?))
+    }
+
+    /// Raises the energy level of an octopus; true if it starts flashing.
+    #[inline]
+    fn charge(&mut self, c: Point) -> bool {
+        let Octopus(energy) = &mut self.0[c];
+        *energy += 1;
+        *energy == MAX_ENERGY + 1
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        self.0.len()
+    }
+
+    fn step(&mut self) -> usize {
+        let mut flashing: Vec<Point> = self.0.points().collect::<Vec<_>>()
+            .into_iter()
+            .filter(|&c| self.charge(c))
+            .collect();
+        let mut exhausted: HashSet<Point> = flashing.iter().copied().collect();
+
+        while let Some(center) = flashing.pop() {
+            let neighbours: Vec<Point> = self.0.neighbours(center, &SURROUNDING).collect();
+            for c in neighbours {
+                if self.charge(c) {
+                    flashing.push(c);
+                    exhausted.insert(c);
+                }
+            }
+        }
+        let result = exhausted.len();
+
+        for c in exhausted {
+            self.0[c] = Octopus(0);
+        }
+
+        result
+    }
+}
+
+impl RenderFrame for Map {
+    fn frame(&self) -> Grid<Cell> {
+        self.0.map(|&Octopus(energy)| {
+            let digit = char::from_digit(energy.min(9) as u32, 10).unwrap_or('+');
+            match energy {
+                0 => Cell::coloured(digit, animation::WHITE),
+                _ => Cell::coloured(digit, animation::grey(energy, 2 * MAX_ENERGY)),
+            }
+        })
+    }
+
+    /// Stops once all octopuses flash together.
+    fn step(&mut self) -> bool {
+        if self.0.cells().all(|&Octopus(energy)| energy == 0) {
+            return false;
+        }
+        Map::step(self);
+        true
+    }
+}
+
+/// A map where every octopus has the same energy, so they flash together
+/// every ten steps from the start; a random map need not ever synchronise.
+fn random_input(rng: &mut Rng) -> Generated {
+    let (width, height) = (rng.size(1..=12), rng.size(1..=12));
+    let energy = rng.below(10) as usize;
+    let row = energy.to_string().repeat(width) + "\n";
+    Generated::answered(row.repeat(height), 10 * width * height, MAX_ENERGY + 1 - energy)
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day11;
+
+impl aoc_common::Solution for Day11 {
+    type Input = Map;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Map, Error> {
+        Map::parse(input)
+    }
+
+    fn part1(map: &Map) -> Result<usize, Error> {
+        let mut map = map.clone();
+        Ok((0..100).map(|_| map.step()).sum())
+    }
+
+    fn part2(map: &Map) -> Result<usize, Error> {
+        let mut map = map.clone();
+        let mut steps = 1;
+        while map.step() != map.size() {
+            steps += 1;
+        }
+        Ok(steps)
+    }
+
+    fn render(map: &Map) -> Result<Option<Image>, Error> {
+        Ok(Some(Image::graymap(&map.0, |&Octopus(energy)| energy as u16)))
+    }
+
+    fn animate(map: &Map) -> Option<Box<dyn RenderFrame>> {
+        Some(Box::new(map.clone()))
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+}
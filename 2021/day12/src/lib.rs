@@ -0,0 +1,187 @@
+//! Advent of Code 2021, day 12: Passage Pathing.
+
+#![warn(missing_docs)]
+
+use petgraph::graph::{NodeIndex, UnGraph};
+use std::collections::{HashMap};
+use itertools::Itertools;
+use itertools::FoldWhile;
+use std::fmt;
+use aoc_common::{Class, Generated, Rng, Search, Span};
+use aoc_common::search::Revisit;
+
+/// Why the paths through the caves cannot be counted.
+#[derive(Clone,Debug)]
+pub enum Error {
+    /// A line that is not two caves joined by `-`.
+    FormatError(Span),
+    /// There is no `start` or no `end` cave.
+    NodeMissing,
+    /// More caves than fit in the visited set.
+    TooManyCaves(usize),
+    /// Two big caves are connected, so paths may go back and forth forever.
+    InfiniteLoopDetected,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::FormatError(_) => write!(f, "Expected a passage like `start-A`."),
+            Error::NodeMissing => write!(f, "The cave needs a `start` and an `end`."),
+            Error::TooManyCaves(n) => write!(f, "Found {} caves, at most 64 are supported.", n),
+            Error::InfiniteLoopDetected => write!(f, "Two large caves are connected; there are infinitely many paths."),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::FormatError(..) => Class::Parse,
+            Error::NodeMissing => Class::Parse,
+            Error::TooManyCaves(_) => Class::Parse,
+            Error::InfiniteLoopDetected => Class::Solve,
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::FormatError(span) => Some(*span),
+            _ => None,
+        }
+    }
+}
+
+struct Node {
+    large: bool,
+}
+
+/// The caves and the passages between them.
+pub struct Cave {
+    map: UnGraph<Node, ()>,
+    source: NodeIndex,
+    target: NodeIndex,
+}
+
+impl Node {
+    fn new(name: &str) -> Self {
+        let large = name.chars().all(|c| c.is_ascii_uppercase() || !c.is_ascii());
+        Node{large}
+    }
+}
+
+impl Cave {
+    fn parse(text: &str) -> Result<Self,Error> {
+        let map: UnGraph<Node,()> = UnGraph::new_undirected();
+        let mut who: HashMap<String, NodeIndex> = HashMap::new();
+        let mut add_node = |name: String, mut map: UnGraph<Node,()>| {
+            let index = match who.get(&name) {
+                Some(&index) => index,
+                None => map.add_node(Node::new(&name))
+            };
+            who.insert(name, index);
+            (index, map)
+        };
+        let lines: Result<Vec<_>,Error> = aoc_common::trimmed(text).into_iter().map(|line| {
+            let (a, b) = line.split_once('-').ok_or(Error::FormatError(Span::of(text, line)))?;
+            Ok((a.to_string(), b.to_string()))
+        }).collect();
+        if let FoldWhile::Continue(map) = lines?.into_iter().fold_while(map, |map, (a,b)| {
+            let (a, map) = add_node(a, map);
+            let (b, map) = add_node(b, map);
+            let mut map = map;
+            map.add_edge(a, b, ());
+            if map[a].large && map[b].large {
+                FoldWhile::Done(map)
+            } else {
+                FoldWhile::Continue(map)
+            }
+        }) {
+            if map.node_count() > 64 {
+                Err(Error::TooManyCaves(map.node_count()))
+            } else if let (Some(&source), Some(&target)) = (who.get("start"), who.get("end")) {
+                Ok(Cave{map,source,target})
+            } else {
+                Err(Error::NodeMissing)
+            }
+        } else {
+            Err(Error::InfiniteLoopDetected)
+        }
+    }
+
+    /// Counts the paths from `start` to `end` that enter small caves at most
+    /// once, except for `revisit_count` revisits. A state is the current
+    /// cave, the set of small caves seen as a bit mask and the revisits so
+    /// far; the search walks the paths depth first and keeps none of them.
+    fn count_paths(&self, revisit_count: usize) -> usize {
+        let successors = |&(cave, seen, revisits): &(NodeIndex, u64, usize)| {
+            let nexts = if cave == self.target { None } else { Some(self.map.neighbors_undirected(cave)) };
+            nexts.into_iter().flatten().filter(|&next| next != self.source).filter_map(move |next| {
+                let bit = 1 << next.index();
+                let revisits = if seen & bit != 0 { revisits + 1 } else { revisits };
+                let seen = if self.map[next].large { seen } else { seen | bit };
+                (revisits <= revisit_count).then_some(((next, seen, revisits), 1))
+            })
+        };
+        Search::new((self.source, 1 << self.source.index(), 0), successors)
+            .visited(Revisit)
+            .depth_first()
+            .forget_paths()
+            .filter(|&((cave, _, _), _)| cave == self.target)
+            .count()
+    }
+}
+
+/// A few caves joined at random, never two large caves together.
+fn random_input(rng: &mut Rng) -> Generated {
+    const SMALL: [&str; 6] = ["xa", "kq", "rd", "pm", "uv", "gi"];
+    const LARGE: [&str; 3] = ["HN", "LZ", "TB"];
+    let mut caves = vec!["start", "end"];
+    caves.extend(&SMALL[..rng.size(1..=SMALL.len())]);
+    caves.extend(&LARGE[..rng.size(0..=LARGE.len())]);
+    let large = |cave: &str| cave.chars().all(|c| c.is_ascii_uppercase());
+    let mut passages = Vec::new();
+    for _ in 0..rng.size(1..=14) {
+        let (a, b) = (*rng.pick(&caves), *rng.pick(&caves));
+        let joined = passages.contains(&(a, b)) || passages.contains(&(b, a));
+        if a != b && !joined && !(large(a) && large(b)) {
+            passages.push((a, b));
+        }
+    }
+    if passages.is_empty() {
+        passages.push(("start", "end"));
+    }
+    let mut text: String = passages.iter().map(|(a, b)| format!("{}-{}\n", a, b)).collect();
+    for cave in ["start", "end"] {
+        if !passages.iter().any(|&(a, b)| a == cave || b == cave) {
+            text += &format!("{}-{}\n", cave, SMALL[0]);
+        }
+    }
+    Generated::text(text)
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day12;
+
+impl aoc_common::Solution for Day12 {
+    type Input = Cave;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Cave, Error> {
+        Cave::parse(input)
+    }
+
+    fn part1(cave: &Cave) -> Result<usize, Error> {
+        Ok(cave.count_paths(0))
+    }
+
+    fn part2(cave: &Cave) -> Result<usize, Error> {
+        Ok(cave.count_paths(1))
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+}
@@ -0,0 +1,287 @@
+//! Advent of Code 2021, day 13: Transparent Origami.
+
+#![warn(missing_docs)]
+
+use std::collections::{BTreeSet, HashSet};
+use regex::Regex;
+use aoc_common::ocr;
+use std::fmt;
+use aoc_common::{Answer, Class, Generated, Grid, Image, RenderFrame, Rng, Span};
+use aoc_common::animation::{self, Cell};
+
+/// Why the transparent paper cannot be folded or read.
+#[derive(Clone,Debug)]
+pub enum Error {
+    /// A line that is not a dot.
+    FormatError(Span),
+    /// The dots are not followed by any instructions.
+    MissingFolds,
+    /// A line after the dots that is not a fold.
+    InvalidFold(String, Span),
+    /// A dot lies on the line being folded along, or so far past it that
+    /// it would land beyond the edge of the paper.
+    ImpossibleFold(Dot, Fold),
+    /// A fold was asked for after the last one.
+    NoFoldRemaining,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::FormatError(_) => write!(f, "Expected a dot like `6,10`."),
+            Error::MissingFolds => write!(f, "No fold instructions after the dots."),
+            Error::InvalidFold(line, _) => write!(f, "Expected a fold like `fold along y=7`, got `{}`.", line),
+            Error::ImpossibleFold(dot, fold) => write!(f, "Dot ({}, {}) cannot be folded along {:?}={}.", dot.x, dot.y, fold.axis, fold.offset),
+            Error::NoFoldRemaining => write!(f, "No fold remaining."),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::FormatError(..) => Class::Parse,
+            Error::MissingFolds => Class::Parse,
+            Error::InvalidFold(..) => Class::Parse,
+            Error::ImpossibleFold(..) => Class::Solve,
+            Error::NoFoldRemaining => Class::Solve,
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::FormatError(span) => Some(*span),
+            Error::InvalidFold(_, span) => Some(*span),
+            _ => None,
+        }
+    }
+}
+
+/// The coordinate a fold line is given by.
+#[derive(Clone,Debug)]
+pub enum Axis {
+    /// A vertical line, `fold along x=...`.
+    X=0,
+    /// A horizontal line, `fold along y=...`.
+    Y=1,
+}
+
+/// A fold along a line of the paper.
+#[derive(Clone,Debug)]
+pub struct Fold {
+    axis: Axis,
+    offset: usize
+}
+
+/// A dot on the transparent paper.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Dot {
+    y: usize,
+    x: usize,
+}
+
+impl Fold {
+    fn new(text: &str, definition: &str) -> Result<Self,Error> {
+        let pattern = Regex::new(r"^fold along (y|x)=(\d{1,9})\s*$").unwrap();
+        let capture = pattern.captures(definition)
+            .ok_or_else(|| Error::InvalidFold(definition.to_string(), Span::of(text, definition)))?;
+        let offset: usize = capture[2].parse().unwrap();
+        match capture[1].chars().next().unwrap() {
+            'x' => Ok(Fold{axis: Axis::X, offset}),
+            'y' => Ok(Fold{axis: Axis::Y, offset}),
+            _ => panic!()
+        }
+    }
+
+    fn apply(&self, dot: &Dot) -> Result<Dot,Error> {
+        let x = dot.x;
+        let y = dot.y;
+        let t = self.offset;
+        match self.axis {
+            Axis::X if x > t && x <= 2*t => Ok(Dot{x:2*t-x,y}),
+            Axis::Y if y > t && y <= 2*t => Ok(Dot{x,y:2*t-y}),
+            Axis::X if x < t => Ok(*dot),
+            Axis::Y if y < t => Ok(*dot),
+            _ => Err(Error::ImpossibleFold(*dot, self.clone()))
+        }
+    }
+}
+
+impl Dot {
+    fn new(text: &str, definition: &str) -> Result<Self,Error> {
+        let pattern = Regex::new(r"^(\d{1,9}),\s*(\d{1,9})\s*$").unwrap();
+        let capture = pattern.captures(definition).ok_or(Error::FormatError(Span::of(text, definition)))?;
+        let x = capture[1].parse().unwrap();
+        let y = capture[2].parse().unwrap();
+        Ok(Dot{x,y})
+    }
+}
+
+/// The dots on the paper and the folds to make.
+#[derive(Clone)]
+pub struct Instructions {
+    dots: HashSet<Dot>,
+    folds: Vec<Fold>
+}
+
+impl Instructions {
+    fn new(definition: &str) -> Result<Self,Error> {
+        let mut dots: HashSet<Dot> = HashSet::new();
+        let mut folds: Vec<Fold> = Vec::new();
+        let mut paragraphs = aoc_common::paragraphs(definition).into_iter();
+        for line in aoc_common::trimmed(paragraphs.next().unwrap_or_default()) {
+            dots.insert(Dot::new(definition, line)?);
+        }
+        for line in aoc_common::trimmed(paragraphs.next().unwrap_or_default()) {
+            folds.push(Fold::new(definition, line)?);
+        }
+        if folds.is_empty() {
+            Err(Error::MissingFolds)
+        } else {
+            folds.reverse();
+            Ok(Instructions{dots,folds})
+        }
+    }
+
+    fn fold_one(&mut self) -> Result<(),Error> {
+        if let Some(fold) = self.folds.pop() {
+            self.dots = self.dots.iter().map(|d| fold.apply(d)).collect::<Result<HashSet<_>,_>>()?;
+            Ok(())
+        } else {
+            Err(Error::NoFoldRemaining)
+        }
+    }
+
+    fn fold_all(&mut self) -> Result<(),Error> {
+        while !self.folds.is_empty() {
+            self.fold_one()?;
+        }
+        Ok(())
+    }
+
+    fn print(&self) -> Vec<String> {
+        let x_max = self.dots.iter().map(|d| d.x).max().unwrap_or(0);
+        let y_max = self.dots.iter().map(|d| d.y).max().unwrap_or(0);
+        (0..=y_max).map(|y| {
+            (0..=x_max).map(|x| if self.dots.contains(&Dot{x,y}) {'#'} else {' '}).collect()
+        }).collect()
+    }
+}
+
+impl RenderFrame for Instructions {
+    fn frame(&self) -> Grid<Cell> {
+        let width = self.dots.iter().map(|d| d.x + 1).max().unwrap_or(0);
+        let height = self.dots.iter().map(|d| d.y + 1).max().unwrap_or(0);
+        Grid::from_fn(width, height, |(x, y)| {
+            if self.dots.contains(&Dot{x,y}) { Cell::coloured('#', animation::YELLOW) } else { Cell::plain(' ') }
+        })
+    }
+
+    fn step(&mut self) -> bool {
+        self.fold_one().is_ok()
+    }
+}
+
+/// Letters drawn as dots and unfolded along random lines past their edge,
+/// each dot staying, mirroring or both, so that folding brings them back.
+fn random_input(rng: &mut Rng) -> Generated {
+    let letters: Vec<char> = ocr::letters().collect();
+    let word: String = (0..rng.size(1..=5)).map(|_| *rng.pick(&letters)).collect();
+    let mut dots = BTreeSet::new();
+    for (y, row) in ocr::draw(&word).unwrap().iter().enumerate() {
+        dots.extend(row.char_indices().filter(|&(_, c)| c == '#').map(|(x, _)| (x, y)));
+    }
+    let mut folds = Vec::new();
+    let mut first = 0;
+    for _ in 0..rng.size(1..=4) {
+        first = dots.len();
+        let vertical = rng.chance(0.5);
+        let edge = dots.iter().map(|&(x, y)| if vertical { x } else { y }).max().unwrap() + 1;
+        let line = edge + rng.size(0..=3);
+        let mut unfolded = BTreeSet::new();
+        for &(x, y) in &dots {
+            let mirror = if vertical { (2 * line - x, y) } else { (x, 2 * line - y) };
+            match rng.below(3) {
+                0 => unfolded.insert((x, y)),
+                1 => unfolded.insert(mirror),
+                _ => unfolded.insert((x, y)) | unfolded.insert(mirror),
+            };
+        }
+        dots = unfolded;
+        folds.push(format!("fold along {}={}\n", if vertical { 'x' } else { 'y' }, line));
+    }
+    let mut dots: Vec<String> = dots.iter().map(|(x, y)| format!("{},{}\n", x, y)).collect();
+    rng.shuffle(&mut dots);
+    folds.reverse();
+    Generated::answered(dots.concat() + "\n" + &folds.concat(), first, word)
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day13;
+
+impl aoc_common::Solution for Day13 {
+    type Input = Instructions;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = Answer;
+
+    fn parse(input: &str) -> Result<Instructions, Error> {
+        Instructions::new(input)
+    }
+
+    fn part1(instructions: &Instructions) -> Result<usize, Error> {
+        let mut instructions = instructions.clone();
+        instructions.fold_one()?;
+        Ok(instructions.dots.len())
+    }
+
+    /// The code the folded dots spell, or the dots themselves when they
+    /// are not letters the OCR knows.
+    fn part2(instructions: &Instructions) -> Result<Answer, Error> {
+        let mut instructions = instructions.clone();
+        instructions.fold_all()?;
+        match ocr::recognise_dots(instructions.dots.iter().map(|dot| (dot.x, dot.y))) {
+            Ok(word) => Ok(word.into()),
+            Err(_) => Ok(Answer::Art(instructions.print())),
+        }
+    }
+
+    fn render(instructions: &Instructions) -> Result<Option<Image>, Error> {
+        let mut instructions = instructions.clone();
+        instructions.fold_all()?;
+        Ok(Some(Image::dots(instructions.dots.iter().map(|dot| (dot.x, dot.y)))))
+    }
+
+    fn animate(instructions: &Instructions) -> Option<Box<dyn RenderFrame>> {
+        Some(Box::new(instructions.clone()))
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+}
+
+#[test]
+fn example() {
+    let input = "6,10\n0,14\n9,10\n0,3\n10,4\n4,11\n6,0\n6,12\n4,1\n0,13\n10,12\n3,4\n3,0\n8,4\n1,10\n2,14\n8,10\n9,0\n\n\
+                 fold along y=7\nfold along x=5\n";
+    let mut instructions = Instructions::new(input).unwrap();
+    instructions.fold_one().unwrap();
+    assert_eq!(instructions.dots.len(), 17);
+    instructions.fold_all().unwrap();
+    assert_eq!(instructions.print(), ["#####", "#   #", "#   #", "#   #", "#####"]);
+    assert_eq!(aoc_common::solve::<Day13>(input).unwrap(),
+        [Answer::from(17), Answer::art(["#####", "#   #", "#   #", "#   #", "#####"])]);
+}
+
+#[test]
+fn folded_letters_are_read() {
+    let input = "0,0\n0,1\n0,2\n0,3\n0,4\n0,5\n1,5\n2,5\n3,5\n\nfold along x=5\n";
+    assert_eq!(aoc_common::solve::<Day13>(input).unwrap()[1], Answer::from("L"));
+}
+
+#[test]
+fn dots_beyond_the_paper_cannot_be_folded() {
+    let mut instructions = Instructions::new("10,0\n\nfold along x=2\n").unwrap();
+    assert!(matches!(instructions.fold_one(), Err(Error::ImpossibleFold(..))));
+}
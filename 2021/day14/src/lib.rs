@@ -0,0 +1,260 @@
+//! Advent of Code 2021, day 14: Extended Polymerization.
+
+#![warn(missing_docs)]
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use regex::Regex;
+use itertools::Itertools;
+use std::fmt;
+use aoc_common::{Class, Generated, References, Rng, Span};
+
+/// Why the polymer cannot be grown.
+#[derive(Clone,Debug)]
+pub enum Error {
+    /// A line that is not a pair insertion rule.
+    InvalidInsertion(String, Span),
+    /// There is no polymer template.
+    ReadError,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidInsertion(line, _) => write!(f, "Expected an insertion like `CH -> B`, got `{}`.", line),
+            Error::ReadError => write!(f, "Input is empty."),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::InvalidInsertion(..) => Class::Parse,
+            Error::ReadError => Class::Parse,
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::InvalidInsertion(_, span) => Some(*span),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone,Debug)]
+struct Counter<T>(HashMap<T,u64>);
+
+impl<T> Counter<T> where T: Hash + Eq {
+    fn new() -> Self { Counter(HashMap::new()) }
+    #[inline]
+    fn get(&self, key: &T) -> u64 { self.0.get(key).copied().unwrap_or(0) }
+    #[inline]
+    fn set(&mut self, key: T, value: u64) { self.0.insert(key, value); }
+    #[inline]
+    fn add(&mut self, key: T, value: u64) { 
+        if value > 0 {
+            let new = self.get(&key) + value;
+            self.set(key, new);
+        }
+    }
+    #[inline]
+    fn sub(&mut self, key: T, value: u64) { 
+        if value > 0 {
+            let new = self.get(&key) - value;
+            self.set(key, new);
+        }
+    }
+    #[inline]
+    fn inc(&mut self, key: T) { self.add(key, 1) }
+
+    fn iter(&self) -> impl Iterator<Item=(&T,&u64)> {
+        self.0.iter()
+    }
+    fn max(&self) -> u64 {
+        self.0.values().copied().max().unwrap_or(0)
+    }
+    fn min(&self) -> u64 {
+        self.0.values().copied().min().unwrap_or(0)
+    }
+}
+
+type Sequence = (u8,u8);
+
+#[derive(Clone,Copy,Debug,Hash,Eq,PartialEq)]
+struct Process {
+    marker: Sequence,
+    link: u8,
+}
+
+/// The polymer template with its pair insertion rules.
+#[derive(Clone,Debug)]
+pub struct Polymer {
+    template: Vec<u8>,
+    sequences: Counter<Sequence>,
+    molecules: Counter<u8>,
+    processes: HashMap<Sequence,Process>,
+}
+
+impl Process {
+    fn new(text: &str, definition: &str) -> Result<Self,Error> {
+        let pattern = Regex::new(r"^([A-Z])([A-Z])\s*->\s*([A-Z])$").unwrap();
+        let capture = pattern.captures(definition)
+            .ok_or_else(|| Error::InvalidInsertion(definition.to_string(), Span::of(text, definition)))?;
+        let convert = |k: usize| capture[k].chars().next().unwrap() as u8;
+        Ok(Process{marker: (convert(1), convert(2)), link: convert(3)})
+    }
+    #[inline]
+    fn m1(&self) -> (u8,u8) { (self.marker.0, self.link) }
+    #[inline]
+    fn m2(&self) -> (u8,u8) { (self.link, self.marker.1) }
+}
+
+impl Polymer {
+    fn new(definition: &str) -> Result<Self,Error> {
+        let mut iter = aoc_common::trimmed(definition).into_iter();
+        let mut processes: HashMap<Sequence,Process> = HashMap::new();
+        let template: Vec<u8> = iter.next().ok_or(Error::ReadError)?.chars().map(|c| c as u8).collect();
+        for line in iter {
+            let process = Process::new(definition, line)?;
+            processes.insert(process.marker, process);
+        }
+        let mut sequences: Counter<Sequence> = Counter::new();
+        let mut molecules: Counter<u8> = Counter::new();
+        template.iter().copied()
+            .inspect(|&k| molecules.inc(k))
+            .tuple_windows()
+            .for_each(|s: Sequence| {sequences.inc(s);});
+        Ok(Polymer{template,sequences,molecules,processes})
+    }
+
+    fn mutate_once(&mut self) {
+        let _span = tracing::debug_span!("mutate_once", pairs = self.sequences.0.len()).entered();
+        let scan: Counter<Process> = Counter(self.processes.values()
+            .map(|&p| (p, self.sequences.get(&p.marker))).collect::<HashMap<_,_>>());
+        for (process, &count) in scan.iter() {
+            self.molecules.add(process.link, count);
+            self.sequences.add(process.m1(), count);
+            self.sequences.add(process.m2(), count);
+        }
+        for (process, &count) in scan.iter() {
+            self.sequences.sub(process.marker, count);
+        }
+    }
+
+    fn mutate(&mut self, age: usize) {
+        for _ in 0..age {
+            self.mutate_once();
+        }
+    }
+
+    fn checksum(&self) -> u64 {
+        self.molecules.max() - self.molecules.min()
+    }
+}
+
+fn checksum_after(polymer: &Polymer, age: usize) -> u64 {
+    let mut polymer = polymer.clone();
+    polymer.mutate(age);
+    polymer.checksum()
+}
+
+/// The checksum after `age` steps, inserting into the chain itself.
+fn checksum_by_insertion(polymer: &Polymer, age: usize) -> u64 {
+    let mut chain = polymer.template.clone();
+    for _ in 0..age {
+        let mut next = Vec::with_capacity(2 * chain.len());
+        for (a, b) in chain.iter().copied().tuple_windows() {
+            next.push(a);
+            if let Some(process) = polymer.processes.get(&(a, b)) {
+                next.push(process.link);
+            }
+        }
+        next.extend(chain.last());
+        chain = next;
+    }
+    let counts = chain.into_iter().counts();
+    let (min, max) = (counts.values().min().unwrap_or(&0), counts.values().max().unwrap_or(&0));
+    (max - min) as u64
+}
+
+/// Letters inserted between `a` and `b` over `age` steps, by letter.
+fn inserted(polymer: &Polymer, pair: Sequence, age: usize, memo: &mut HashMap<(Sequence, usize), Counter<u8>>) -> Counter<u8> {
+    if let Some(counts) = memo.get(&(pair, age)) {
+        return counts.clone();
+    }
+    let mut counts = Counter::new();
+    if let Some(process) = polymer.processes.get(&pair).filter(|_| age > 0) {
+        counts.inc(process.link);
+        for half in [process.m1(), process.m2()] {
+            for (&letter, &count) in inserted(polymer, half, age - 1, memo).iter() {
+                counts.add(letter, count);
+            }
+        }
+    }
+    memo.insert((pair, age), counts.clone());
+    counts
+}
+
+/// The checksum after `age` steps, expanding every pair of the template
+/// on its own and remembering what each pair grows into.
+fn checksum_by_recursion(polymer: &Polymer, age: usize) -> u64 {
+    let mut memo = HashMap::new();
+    let mut counts = Counter::new();
+    for &letter in &polymer.template {
+        counts.inc(letter);
+    }
+    for pair in polymer.template.iter().copied().tuple_windows() {
+        for (&letter, &count) in inserted(polymer, pair, age, &mut memo).iter() {
+            counts.add(letter, count);
+        }
+    }
+    counts.max() - counts.min()
+}
+
+fn random_input(rng: &mut Rng) -> Generated {
+    let mut letters: Vec<char> = ('A'..='Z').collect();
+    rng.shuffle(&mut letters);
+    letters.truncate(rng.size(1..=8));
+    let mut text: String = (0..rng.size(1..=20)).map(|_| *rng.pick(&letters)).collect();
+    text += "\n\n";
+    for &a in &letters {
+        for &b in &letters {
+            if rng.chance(0.9) {
+                text += &format!("{}{} -> {}\n", a, b, rng.pick(&letters));
+            }
+        }
+    }
+    Generated::text(text)
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day14;
+
+impl aoc_common::Solution for Day14 {
+    type Input = Polymer;
+    type Error = Error;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Polymer, Error> {
+        Polymer::new(input)
+    }
+
+    fn part1(polymer: &Polymer) -> Result<u64, Error> {
+        Ok(checksum_after(polymer, 10))
+    }
+
+    fn part2(polymer: &Polymer) -> Result<u64, Error> {
+        Ok(checksum_after(polymer, 40))
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+
+    fn reference(polymer: &Polymer) -> Option<References> {
+        Some([Some(checksum_by_insertion(polymer, 10).into()), Some(checksum_by_recursion(polymer, 40).into())])
+    }
+}
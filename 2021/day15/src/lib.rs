@@ -0,0 +1,147 @@
+//! Advent of Code 2021, day 15: Chiton.
+
+#![warn(missing_docs)]
+
+use std::fmt;
+use aoc_common::{Class, Generated, Grid, GridError, Image, Rng, Search, Span};
+use aoc_common::grid::{Point, ORTHOGONAL};
+
+/// Why the safest path through the cave cannot be found.
+#[derive(Clone,Debug)]
+pub enum Error {
+    /// The bottom right corner cannot be reached.
+    NoPathFound,
+    /// The map was asked to be tiled zero times.
+    ZeroScale,
+    /// A row that is shorter or longer than the first.
+    NonSquareMap(Span),
+    /// The map has no rows.
+    InvalidState,
+    /// A risk level that is not a digit from 1 to 9.
+    InvalidCharacter(char, Span),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::NoPathFound => write!(f, "No path leads to the bottom right corner."),
+            Error::ZeroScale => write!(f, "Cannot scale the map by zero."),
+            Error::NonSquareMap(_) => write!(f, "Map is not rectangular."),
+            Error::InvalidState => write!(f, "Map is empty."),
+            Error::InvalidCharacter(c, _) => write!(f, "Invalid risk level: {}", c),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::NoPathFound => Class::Solve,
+            Error::ZeroScale => Class::Solve,
+            Error::NonSquareMap(..) => Class::Parse,
+            Error::InvalidState => Class::Parse,
+            Error::InvalidCharacter(..) => Class::Parse,
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::NonSquareMap(span) => Some(*span),
+            Error::InvalidCharacter(_, span) => Some(*span),
+            _ => None,
+        }
+    }
+}
+
+impl From<GridError> for Error {
+    fn from(error: GridError) -> Self {
+        match error {
+            GridError::InvalidCharacter(c, span) => Error::InvalidCharacter(c, span),
+            GridError::RaggedRow(span) => Error::NonSquareMap(span),
+            GridError::Empty => Error::InvalidState,
+        }
+    }
+}
+
+/// The risk level of every position in the cave.
+#[derive(Clone)]
+pub struct NavigationalSystem(Grid<u32>);
+
+impl NavigationalSystem {
+    fn parse(text: &str) -> Result<Self,Error> {
+        Ok(NavigationalSystem(Grid::parse(text, |c| c.to_digit(10).filter(|&risk| risk > 0))?))
+    }
+
+    fn navigate(&self) -> Result<u32,Error> {
+        let NavigationalSystem(grid) = self;
+        let target: Point = (grid.width() - 1, grid.height() - 1);
+        Search::new((0, 0), |&spot: &Point| grid
+                .neighbours(spot, &ORTHOGONAL)
+                .map(|n| (n, grid[n] as usize))
+            )
+            .visited(grid.map(|_| None))
+            .buckets()
+            .heuristic(|&(x, y)| (target.0 - x) + (target.1 - y))
+            .find(|&spot| spot == target)
+            .map(|path| path.cost as u32)
+            .ok_or(Error::NoPathFound)
+    }
+
+    fn scaled(&self, scale: usize) -> Result<Self,Error> {
+        let NavigationalSystem(grid) = self;
+        if scale < 1 {
+            return Err(Error::ZeroScale);
+        }
+        let (width, depth) = (grid.width(), grid.height());
+        Ok(NavigationalSystem(Grid::from_fn(width * scale, depth * scale, |(x, y)| {
+            let risk = grid[(x % width, y % depth)] + (x / width) as u32 + (y / depth) as u32;
+            (risk + 8) % 9 + 1
+        })))
+    }
+}
+
+fn random_input(rng: &mut Rng) -> Generated {
+    let width = rng.size(1..=30);
+    let rows: Vec<String> = (0..rng.size(1..=30))
+        .map(|_| (0..width).map(|_| char::from(b'0' + rng.range(1..=9) as u8)).collect::<String>() + "\n")
+        .collect();
+    Generated::text(rows.concat())
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day15;
+
+impl aoc_common::Solution for Day15 {
+    type Input = NavigationalSystem;
+    type Error = Error;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<NavigationalSystem, Error> {
+        NavigationalSystem::parse(input)
+    }
+
+    fn part1(system: &NavigationalSystem) -> Result<u32, Error> {
+        system.navigate()
+    }
+
+    fn part2(system: &NavigationalSystem) -> Result<u32, Error> {
+        system.scaled(5)?.navigate()
+    }
+
+    fn render(system: &NavigationalSystem) -> Result<Option<Image>, Error> {
+        Ok(Some(Image::graymap(&system.0, |&risk| risk as u16)))
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+}
+
+#[test]
+fn zero_risk_is_rejected() {
+    match NavigationalSystem::parse("19\n01\n") {
+        Err(Error::InvalidCharacter('0', span)) => assert_eq!((span.line, span.column), (2, 1)),
+        _ => panic!("a risk of 0 must not parse"),
+    }
+}
@@ -0,0 +1,351 @@
+//! Advent of Code 2021, day 16: Packet Decoder.
+
+#![warn(missing_docs)]
+
+use bitreader::{BitReader,BitReaderError};
+use hex::FromHex;
+use std::fmt;
+use aoc_common::{Class, Generated, Rng, Span};
+
+/// Why a transmission cannot be decoded into a [`Packet`] or evaluated.
+#[derive(Clone,Debug)]
+pub enum Error {
+    /// The transmission is not whole bytes of hexadecimal digits.
+    InvalidHexEncoding(Span),
+    /// The value overflows, or an operator lacks the operands it needs.
+    RuntimeError,
+    /// The bits end before the packet does.
+    ParsingFailure(BitReaderError, Span),
+    /// A literal needs more than 64 bits.
+    LiteralTooLarge(Span),
+    /// Operators are nested deeper than the decoder allows.
+    NestedTooDeeply(Span),
+    /// The sub-packets of an operator end after its length in bits.
+    LengthMismatch(Span),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidHexEncoding(_) => write!(f, "Expected an even number of hexadecimal digits."),
+            Error::RuntimeError => write!(f, "Packet cannot be evaluated."),
+            Error::ParsingFailure(e, _) => write!(f, "Malformed packet: {}", e),
+            Error::LiteralTooLarge(_) => write!(f, "Literal value does not fit in 64 bits."),
+            Error::NestedTooDeeply(_) => write!(f, "Packets are nested more than {} deep.", MAX_DEPTH),
+            Error::LengthMismatch(_) => write!(f, "Sub-packets run past their declared length."),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::InvalidHexEncoding(..) => Class::Parse,
+            Error::RuntimeError => Class::Solve,
+            Error::ParsingFailure(..) => Class::Parse,
+            Error::LiteralTooLarge(..) => Class::Parse,
+            Error::NestedTooDeeply(..) => Class::Parse,
+            Error::LengthMismatch(..) => Class::Parse,
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::InvalidHexEncoding(span) => Some(*span),
+            Error::ParsingFailure(_, span) => Some(*span),
+            Error::LiteralTooLarge(span) => Some(*span),
+            Error::NestedTooDeeply(span) => Some(*span),
+            Error::LengthMismatch(span) => Some(*span),
+            _ => None,
+        }
+    }
+}
+
+/// Operators nested deeper than this are rejected rather than risk the
+/// stack.
+const MAX_DEPTH: usize = 64;
+
+/// Why the bits do not form a packet.
+enum Malformed {
+    Bits(BitReaderError),
+    LiteralTooLarge,
+    TooDeep,
+    LengthMismatch,
+}
+
+impl From<BitReaderError> for Malformed {
+    fn from(e: BitReaderError) -> Self {
+        Malformed::Bits(e)
+    }
+}
+
+#[derive(Clone,Copy,Hash,PartialEq,Eq,PartialOrd,Ord)]
+enum TypeId {
+    Sum,
+    Mul,
+    Min,
+    Max,
+    CheckGT,
+    CheckLT,
+    CheckEQ,
+}
+
+#[derive(Clone,Copy,Hash,PartialEq,Eq)]
+enum LengthType {
+    TotalLength = 0,
+    PacketCount = 1,
+}
+
+enum PacketBody {
+    Literal(u64),
+    Operator {
+        method: TypeId,
+        packets: Vec<Packet>
+    }
+}
+
+/// A decoded BITS transmission: a literal value, or an operator over
+/// sub-packets.
+pub struct Packet {
+    version: u8,
+    body: PacketBody
+}
+
+impl LengthType {
+    fn new(reader: &mut BitReader) -> bitreader::Result<Self> {
+        Ok(if reader.read_bool()? { LengthType::PacketCount } else { LengthType::TotalLength })
+    }
+}
+
+impl PacketBody {
+    fn new(reader: &mut BitReader, depth: usize) -> Result<Self, Malformed> {
+        let id = reader.read_u8(3)?;
+        if id == 4 {
+            let mut value: u64 = 0;
+            loop {
+                let done = reader.read_u8(1)? == 0;
+                value = value.checked_mul(0b10000).ok_or(Malformed::LiteralTooLarge)?;
+                value += reader.read_u64(4)?;
+                if done { break; }
+            }
+            Ok(PacketBody::Literal(value))
+        } else {
+            let method = match id {
+                0 => TypeId::Sum,
+                1 => TypeId::Mul,
+                2 => TypeId::Min,
+                3 => TypeId::Max,
+                5 => TypeId::CheckGT,
+                6 => TypeId::CheckLT,
+                _ => TypeId::CheckEQ,
+            };
+            if depth >= MAX_DEPTH {
+                return Err(Malformed::TooDeep);
+            }
+            let packets: Vec<_> = match LengthType::new(reader)? {
+                LengthType::PacketCount => {
+                    let t = reader.read_u16(11)?;
+                    (0..t).map(|_| Packet::new(reader, depth + 1)).collect::<Result<_, _>>()?
+                },
+                LengthType::TotalLength => {
+                    let mut remaining = reader.read_u16(15)? as usize;
+                    let mut packets: Vec<Packet> = Vec::new();
+                    while remaining > 0 {
+                        let start = reader.position();
+                        packets.push(Packet::new(reader, depth + 1)?);
+                        let length = (reader.position() - start) as usize;
+                        remaining = remaining.checked_sub(length).ok_or(Malformed::LengthMismatch)?;
+                    }
+                    packets
+                }
+            };
+            Ok(PacketBody::Operator{
+                packets,
+                method,
+            })
+        }
+    }
+
+    fn chk(&self) -> usize {
+        if let PacketBody::Operator{method:_,packets} = self {
+            packets.iter().map(|p| p.version_sum()).sum()
+        } else { 0 }
+    }
+
+    fn value(&self) -> Option<u64> {
+        match self {
+            PacketBody::Literal(value) => Some(*value),
+            PacketBody::Operator{method,packets} => {
+                let values: Option<Vec<u64>> = packets.iter().map(|p| p.value()).collect();
+                let mut it = values?.into_iter();
+                Some(match method {
+                    TypeId::Sum => it.try_fold(0, u64::checked_add)?,
+                    TypeId::Mul => it.try_fold(1, u64::checked_mul)?,
+                    TypeId::Min => it.min()?,
+                    TypeId::Max => it.max()?,
+                    _ => {
+                        let a = it.next()?;
+                        let b = it.next()?;
+                        if match method {
+                            TypeId::CheckGT => a > b,
+                            TypeId::CheckLT => a < b,
+                            TypeId::CheckEQ => a == b,
+                            _ => false
+                        } {1} else {0}
+                    },
+                })
+            }
+        }
+    }
+}
+
+impl Packet {
+    /// Decodes the outermost packet of a hexadecimal transmission; spans in
+    /// errors point into `text`.
+    pub fn decode(text: &str) -> Result<Self, Error> {
+        let hex = text.trim();
+        let data: Vec<u8> = Vec::from_hex(hex).map_err(|e| Error::InvalidHexEncoding(match e {
+            hex::FromHexError::InvalidHexCharacter{index, ..} => Span::at(text, hex, index),
+            _ => Span::of(text, hex),
+        }))?;
+        let mut reader = BitReader::new(&data);
+        Packet::new(&mut reader, 0).map_err(|e| {
+            let digit = (reader.position() as usize / 4).min(hex.len().saturating_sub(1));
+            let span = Span::at(text, hex, digit);
+            match e {
+                Malformed::Bits(e) => Error::ParsingFailure(e, span),
+                Malformed::LiteralTooLarge => Error::LiteralTooLarge(span),
+                Malformed::TooDeep => Error::NestedTooDeeply(span),
+                Malformed::LengthMismatch => Error::LengthMismatch(span),
+            }
+        })
+    }
+
+    fn new(reader: &mut BitReader, depth: usize) -> Result<Self, Malformed> {
+        let version = reader.read_u8(3)?;
+        let body = PacketBody::new(reader, depth)?;
+        Ok(Packet{version,body})
+    }
+
+    /// The version in the packet's header.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// The sub-packets of an operator; none for a literal.
+    pub fn packets(&self) -> &[Packet] {
+        match &self.body {
+            PacketBody::Literal(_) => &[],
+            PacketBody::Operator{packets, ..} => packets,
+        }
+    }
+
+    /// The versions of this packet and all packets nested in it, added up.
+    pub fn version_sum(&self) -> usize {
+        self.body.chk() + self.version as usize
+    }
+
+    /// What the packet evaluates to; `None` if that overflows or an
+    /// operator is missing operands.
+    pub fn value(&self) -> Option<u64> {
+        self.body.value()
+    }
+}
+
+/// The lowest `width` bits of `value`, most significant first.
+fn bits_of(value: u64, width: usize) -> impl Iterator<Item = bool> {
+    (0..width).rev().map(move |k| value >> k & 1 == 1)
+}
+
+/// Appends a random packet to `bits` and returns its version sum and value.
+/// Literals sometimes carry leading zero groups, and an operator whose value
+/// would overflow is replaced by a literal.
+fn random_packet(rng: &mut Rng, depth: usize, bits: &mut Vec<bool>) -> (u64, u64) {
+    let start = bits.len();
+    let version = rng.below(8);
+    bits.extend(bits_of(version, 3));
+    if depth < 4 && rng.chance(0.6) {
+        let id = *rng.pick(&[0, 1, 2, 3, 5, 6, 7]);
+        let count = if id >= 5 { 2 } else { rng.size(1..=4) };
+        let width = if rng.chance(0.5) { 11 } else { 15 };
+        bits.extend(bits_of(id, 3).chain([width == 11]).chain(bits_of(0, width)));
+        let header = bits.len();
+        let (mut versions, mut values) = (version, Vec::new());
+        for _ in 0..count {
+            let (v, value) = random_packet(rng, depth + 1, bits);
+            versions += v;
+            values.push(value);
+        }
+        let length = if width == 11 { count } else { bits.len() - header };
+        bits.splice(header - width..header, bits_of(length as u64, width));
+        let mut values = values.into_iter();
+        let value = match id {
+            0 => values.try_fold(0, u64::checked_add),
+            1 => values.try_fold(1, u64::checked_mul),
+            2 => values.min(),
+            3 => values.max(),
+            _ => {
+                let (a, b) = (values.next().unwrap(), values.next().unwrap());
+                Some(match id { 5 => a > b, 6 => a < b, _ => a == b } as u64)
+            },
+        };
+        if let Some(value) = value {
+            return (versions, value);
+        }
+        bits.truncate(start + 3);
+    }
+    let magnitude = rng.size(0..=20);
+    let value = rng.below(1 << magnitude);
+    let groups = (64 - value.leading_zeros() as usize).div_ceil(4).max(1) + rng.size(0..=1);
+    bits.extend(bits_of(4, 3));
+    for k in (0..groups).rev() {
+        bits.extend([k > 0].into_iter().chain(bits_of(value >> (4 * k), 4)));
+    }
+    (version, value)
+}
+
+fn random_input(rng: &mut Rng) -> Generated {
+    let mut bits = Vec::new();
+    let (versions, value) = random_packet(rng, 0, &mut bits);
+    bits.resize(bits.len().div_ceil(8) * 8, false);
+    let hex: String = bits.chunks(4)
+        .map(|nibble| format!("{:X}", nibble.iter().fold(0, |n, &bit| 2 * n + bit as u8)))
+        .collect();
+    Generated::answered(hex + "\n", versions as usize, value)
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day16;
+
+impl aoc_common::Solution for Day16 {
+    type Input = Packet;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Packet, Error> {
+        Packet::decode(input)
+    }
+
+    fn part1(packet: &Packet) -> Result<usize, Error> {
+        Ok(packet.version_sum())
+    }
+
+    fn part2(packet: &Packet) -> Result<u64, Error> {
+        packet.value().ok_or(Error::RuntimeError)
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+}
+
+#[test]
+fn operators_hold_their_sub_packets() {
+    let packet = Packet::decode("38006F45291200\n").unwrap();
+    assert_eq!(packet.version(), 1);
+    let values: Vec<_> = packet.packets().iter().map(Packet::value).collect();
+    assert_eq!(values, [Some(10), Some(20)]);
+    assert_eq!(packet.value(), Some(1));
+    assert_eq!(Packet::decode("C200B40A82").unwrap().value(), Some(3));
+}
@@ -0,0 +1,283 @@
+//! Advent of Code 2021, day 17: Trick Shot.
+
+#![warn(missing_docs)]
+
+use regex::Regex;
+use std::collections::HashSet;
+use std::cmp::{max, min};
+use std::fmt;
+use aoc_common::{Class, Generated, References, Rng, Span};
+
+
+/// Why the target area cannot be read.
+#[derive(Clone,Debug)]
+pub enum Error {
+    /// A bound of the area is missing.
+    UnexpectedParsingError,
+    /// A bound that does not fit in an `isize`.
+    InvalidNumber(Span),
+    /// The input is not a target area.
+    PatternMismatch(Span),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::UnexpectedParsingError => write!(f, "Target area is incomplete."),
+            Error::InvalidNumber(_) => write!(f, "Coordinate out of range."),
+            Error::PatternMismatch(_) => write!(f, "Expected a target area like `target area: x=20..30, y=-10..-5`."),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::UnexpectedParsingError => Class::Parse,
+            Error::InvalidNumber(..) => Class::Parse,
+            Error::PatternMismatch(..) => Class::Parse,
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::InvalidNumber(span) => Some(*span),
+            Error::PatternMismatch(span) => Some(*span),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+#[derive(Default)]
+struct Point {
+    x: isize,
+    y: isize,
+}
+
+/// The target area.
+#[derive(Debug, Clone, Copy)]
+pub struct Area {
+    min: Point,
+    max: Point,
+}
+
+
+struct ArcIteratorY<'a> {
+    area: &'a Area,
+    time: isize,
+    y: isize,
+    time_max: isize,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Shot {
+    velocity: isize,
+    time: isize,
+}
+
+impl<'a> ArcIteratorY<'a> {
+    fn new(area: &'a Area) -> Self {
+        ArcIteratorY {
+            area,
+            time: 1,
+            y: area.min.y,
+            time_max: 2 * max(area.min.y.abs(), area.max.y.abs())
+        }
+    }
+}
+
+struct ArcIteratorX<'a> {
+    area: &'a Area,
+    time: isize,
+    dx: Option<isize>,
+}
+
+impl<'a> ArcIteratorX<'a> {
+    fn stop(&self, d: isize) -> isize {
+        let m = self.time;
+        if d <= m { (d * (d + 1)) / 2 } else { m * d - ((m - 1) * m) / 2 }
+    }
+
+    fn new(area: &'a Area, time: isize) -> Self {
+        ArcIteratorX { area, time, dx: Some(0) }
+    }
+}
+
+impl<'a> Iterator for ArcIteratorY<'a> {
+    type Item = Shot;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for t in self.time..=self.time_max {
+            for y in self.y..=self.area.max.y {
+                if 2 * y % t != 0 {
+                    continue;
+                }
+                let dy = (2 * y / t) + t - 1;
+                if dy % 2 != 0 {
+                    continue;
+                }
+                self.y = y + 1;
+                self.time = t;
+                return Some(Shot{velocity: dy / 2, time: t});
+            }
+            self.y = self.area.min.y;
+            self.time = t + 1;
+        }
+        None
+    }
+}
+
+impl<'a> Iterator for ArcIteratorX<'a> {
+    type Item = isize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(dx) = self.dx {
+            if dx <= 0 {
+                let dx = -dx;
+                for k in dx.. {
+                    let stop = -self.stop(k);
+                    if stop < self.area.min.x {
+                        if self.area.max.x > 0 {
+                            self.dx = Some(1);
+                            return self.next();
+                        }
+                        self.dx = None;
+                        break;
+                    } else if stop <= self.area.max.x {
+                        self.dx = Some(-k - 1);
+                        return Some(-k);
+                    }
+                }
+            } else {
+                for k in dx.. {
+                    let stop = self.stop(k);
+                    if stop > self.area.max.x {
+                        self.dx = None;
+                        break;
+                    } else if stop >= self.area.min.x {
+                        self.dx = Some(k + 1);
+                        return Some(k);
+                    }
+                }
+            }
+        }
+        self.dx
+    }
+
+}
+
+impl Area {
+    fn new(spec: &str) -> Result<Self, Error> {
+        let pattern = Regex::new(
+            r"x=(-?\d+)\.\.(-?\d+),\s*y=(-?\d+)\.\.(-?\d+)").unwrap();
+        if let Some(captures) = pattern.captures(spec) {
+            let captures: Option<Vec<_>> = captures.iter().skip(1).collect();
+            let values = captures.ok_or(Error::UnexpectedParsingError)?;
+            let captures: Vec<isize> = values.iter()
+                .map(|&m| m.as_str().parse().map_err(|_| Error::InvalidNumber(Span::of(spec, m.as_str()))))
+                .collect::<Result<_, _>>()?;
+            Ok(Area{
+                min: Point { x: captures[0], y: captures[2] },
+                max: Point { x: captures[1], y: captures[3] },
+            })
+        } else {
+            Err(Error::PatternMismatch(Span::of(spec, spec.trim())))
+        }
+    }
+
+    fn count_possible_shots(&self) -> usize {
+        let velocities: HashSet<(isize, isize)> = ArcIteratorY::new(self)
+            .flat_map(|a| ArcIteratorX::new(self, a.time).map(move |x| (x, a.velocity)))
+            .collect();
+        velocities.len()
+    }
+
+    /// The apex of the shot, if it ever is inside the area after a step.
+    fn simulate(&self, mut dx: isize, mut dy: isize) -> Option<isize> {
+        let (mut x, mut y, mut apex) = (0, 0, 0);
+        while y >= self.min.y || dy >= 0 {
+            x += dx;
+            y += dy;
+            dx -= dx.signum();
+            dy -= 1;
+            apex = max(apex, y);
+            if (self.min.x..=self.max.x).contains(&x) && (self.min.y..=self.max.y).contains(&y) {
+                return Some(apex);
+            }
+        }
+        None
+    }
+
+    /// The apexes of every shot that hits the area, tried one step at a
+    /// time; the area must lie below the launcher.
+    fn simulated_hits(&self) -> Vec<isize> {
+        let reach = max(self.min.y.abs(), self.max.y.abs());
+        (min(0, self.min.x)..=max(0, self.max.x))
+            .flat_map(|dx| (self.min.y..=reach).filter_map(move |dy| self.simulate(dx, dy)))
+            .collect()
+    }
+
+    fn highest_altitude(&self) -> isize {
+        let mut best_apex = 0;
+        for shot in ArcIteratorY::new(self) {
+            let d = shot.velocity;
+            let m = shot.time;
+            if ArcIteratorX::new(self, m).next().is_none() {
+                continue;
+            }
+            let apex = if d <= 0 { 0 } else if d <= m { (d * (d + 1)) / 2 } else { m * d - ((m - 1) * m) / 2 };
+            if apex > best_apex {
+                best_apex = apex;
+            }
+        }
+        best_apex
+    }
+
+}
+
+/// A target area below the launcher, to either side of it.
+fn random_input(rng: &mut Rng) -> Generated {
+    let (near, far) = (rng.range(1..=60), rng.range(0..=30));
+    let (low, high) = (rng.range(-60..=-1), rng.range(0..=30));
+    let (x1, x2) = if rng.chance(0.8) { (near, near + far) } else { (-near - far, -near) };
+    Generated::text(format!("target area: x={}..{}, y={}..{}\n", x1, x2, low, (low + high).min(-1)))
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day17;
+
+impl aoc_common::Solution for Day17 {
+    type Input = Area;
+    type Error = Error;
+    type Part1 = isize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Area, Error> {
+        Area::new(input)
+    }
+
+    fn part1(area: &Area) -> Result<isize, Error> {
+        Ok(area.highest_altitude())
+    }
+
+    fn part2(area: &Area) -> Result<usize, Error> {
+        Ok(area.count_possible_shots())
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+
+    fn reference(area: &Area) -> Option<References> {
+        let hits = area.simulated_hits();
+        Some([Some(hits.iter().copied().max().unwrap_or(0).into()), Some(hits.len().into())])
+    }
+}
+
+#[test]
+fn example() {
+    let area = Area::new("target area: x=20..30, y=-10..-5").unwrap();
+    assert_eq!(area.highest_altitude(), 45);
+    assert_eq!(area.count_possible_shots(), 112);
+}
@@ -0,0 +1,284 @@
+//! Advent of Code 2021, day 19: Beacon Scanner.
+
+#![warn(missing_docs)]
+
+/// Rotations of the scanners.
+pub mod matrix;
+/// Positions of beacons and scanners.
+pub mod vector;
+
+use std::collections::{HashSet, HashMap};
+
+use vector::Vector;
+use matrix::Matrix;
+use std::fmt;
+use tracing::field;
+use aoc_common::{Class, Generated, Rng, Span};
+
+const MINIMUM_OVERLAP_FOR_ALIGNMENT: usize = 12;
+
+
+#[derive(Copy,Clone)]
+#[allow(dead_code)]
+struct Transformation(Matrix, Vector);
+
+
+struct Scan {
+    blips: HashSet<Vector>,
+    edges: HashMap<isize, HashSet<(Vector, Vector)>>,
+    transformation: Option<Transformation>
+}
+
+
+/// Every beacon and scanner, relative to the first scanner.
+pub struct Survey {
+    beacons: HashSet<Vector>,
+    scanners: Vec<Vector>,
+}
+
+
+/// Why the scanner reports cannot be assembled into a [`Survey`].
+#[derive(Debug,Clone)]
+pub enum Error {
+    /// A line under a scanner header that is not a beacon's position.
+    InvalidPoint(String, Span),
+    /// There are no scanners.
+    EmptyInput,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidPoint(line, _) => write!(f, "Expected a beacon like `-618,-824,-621`, got `{}`.", line),
+            Error::EmptyInput => write!(f, "Input is empty."),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::InvalidPoint(..) => Class::Parse,
+            Error::EmptyInput => Class::Parse,
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::InvalidPoint(_, span) => Some(*span),
+            _ => None,
+        }
+    }
+}
+
+
+impl Scan {
+    fn refresh_edges(&mut self) {
+        for &v in self.blips.iter() {
+            for &w in self.blips.iter() {
+                if v == w { continue; }
+                let d = (w - v).abs();
+                self.edges.entry(d).or_default();
+                self.edges.get_mut(&d).unwrap().insert((v,w));
+            }
+        }
+    }
+}
+
+
+impl From<HashSet<Vector>> for Scan {
+    fn from(blips: HashSet<Vector>) -> Self {
+        let mut s = Scan{
+            blips, edges: HashMap::new(), transformation: None};
+        s.refresh_edges();
+        s
+    }
+}
+
+
+impl Scan {
+
+    fn align(&mut self, them: &mut Self) -> Option<Transformation> {
+        let span = tracing::debug_span!("align", beacons = self.blips.len(), tried = field::Empty).entered();
+        let mut tried = 0;
+        let e1: HashSet<isize> = self.edges.keys().copied().collect();
+        let e2: HashSet<isize> = them.edges.keys().copied().collect();
+        let mut shared: Vec<isize> = e1.intersection(&e2).copied().collect();
+        shared.sort_by(|d1, d2| {
+            let n1 = self.edges.get(d1).unwrap().len();
+            let n2 = self.edges.get(d2).unwrap().len();
+            n1.partial_cmp(&n2).unwrap()
+        });
+        for d in shared.iter() {
+            for &(v1, v2) in self.edges.get(d).unwrap().iter() {
+                for &(w1, w2) in them.edges.get(d).unwrap().iter() {
+                    let v = v2 - v1;
+                    let w = w2 - w1;
+                    if let Some(a) = w.rotates_into(&v) {
+                        tried += 1;
+                        let t = v1 - a * w1;
+                        let h: HashSet<Vector> = them.blips.iter().map(|&w| a * w + t).collect();
+                        let n = self.blips.iter().filter(|b| h.contains(b)).count();
+                        if n >= MINIMUM_OVERLAP_FOR_ALIGNMENT {
+                            h.into_iter().for_each(|b| {self.blips.insert(b);});
+                            self.refresh_edges();
+                            let t = Some(Transformation(a,t));
+                            them.transformation = t;
+                            span.record("tried", tried);
+                            return t;
+                        }
+                    }
+                }
+            }
+        }
+        span.record("tried", tried);
+        None
+    }
+
+    fn parse(text: &str) -> Result<Vec<Scan>,Error> {
+        let mut blips: HashSet<Vector> = HashSet::new();
+        let mut scans: Vec<Scan> = Vec::new();
+        for line in aoc_common::trimmed(text) {
+            if line.starts_with("---") {
+                if !blips.is_empty() {
+                    scans.push(Scan::from(blips));
+                    blips = HashSet::new();
+                }
+            } else {
+                let invalid = || Error::InvalidPoint(line.to_string(), Span::of(text, line));
+                let blip: Vec<_> = line.split(',').map(|t| t.parse().map_err(|_| invalid()))
+                    .collect::<Result<_,_>>()?;
+                let &[x, y, z] = blip.as_slice() else { return Err(invalid()) };
+                let blip = Vector{x, y, z};
+                blips.insert(blip);
+            }
+        }
+        if !blips.is_empty() {
+            scans.push(Scan::from(blips));
+        }
+        Ok(scans)
+    }
+
+}
+
+
+impl Survey {
+    /// Aligns the scanner reports in `text` with the first one.
+    pub fn read(text: &str) -> Result<Self,Error> {
+        Survey::assemble(Scan::parse(text)?)
+    }
+
+    /// Every beacon, relative to the first scanner.
+    pub fn beacons(&self) -> &HashSet<Vector> {
+        &self.beacons
+    }
+
+    /// The scanners' positions, the first at the origin.
+    pub fn scanners(&self) -> &[Vector] {
+        &self.scanners
+    }
+
+    fn assemble(mut scans: Vec<Scan>) -> Result<Self,Error> {
+        let mut scanners: Vec<Vector> = Vec::with_capacity(scans.len());
+        scanners.push(Vector::from((0,0,0)));
+
+        scans.reverse();
+        let mut core = scans.pop().ok_or(Error::EmptyInput)?;
+        let mut done = false;
+
+        while !done {
+            done = true;
+            for scan in scans.iter_mut() {
+                if scan.transformation.is_none() {
+                    done = false;
+                    if let Some(Transformation(_,t)) = core.align(scan) {
+                        scanners.push(t);
+                    }
+                }
+            }
+        }
+
+        Ok(Survey{beacons: core.blips, scanners})
+    }
+
+    /// The largest Manhattan distance between two scanners.
+    pub fn max_distance(&self) -> isize {
+        let mut max_distance = 0;
+        for &a in self.scanners.iter() {
+            for &b in self.scanners.iter() {
+                max_distance = std::cmp::max((b - a).abs(), max_distance);
+            }
+        }
+        max_distance
+    }
+}
+
+
+fn random_offset(rng: &mut Rng, reach: i64) -> Vector {
+    Vector::from((rng.range(-reach..=reach) as isize, rng.range(-reach..=reach) as isize, rng.range(-reach..=reach) as isize))
+}
+
+/// Scanners placed one after another near an earlier one, each sharing
+/// exactly enough of its beacons to be aligned, and reporting them in one of
+/// the rotations of [`Matrix::rotation_walk`].
+fn random_input(rng: &mut Rng) -> Generated {
+    let mut scanners = vec![Vector::from((0, 0, 0))];
+    let mut seen: Vec<Vec<Vector>> = vec![(0..rng.size(12..=20)).map(|_| random_offset(rng, 1000)).collect()];
+    for _ in 0..rng.size(1..=4) {
+        let parent = rng.below(scanners.len() as u64) as usize;
+        let position = scanners[parent] + random_offset(rng, 1200);
+        let mut beacons = seen[parent].clone();
+        rng.shuffle(&mut beacons);
+        beacons.truncate(MINIMUM_OVERLAP_FOR_ALIGNMENT);
+        beacons.extend((0..rng.size(0..=8)).map(|_| position + random_offset(rng, 1000)));
+        scanners.push(position);
+        seen.push(beacons);
+    }
+    let mut beacons: Vec<Vector> = Vec::new();
+    for &beacon in seen.iter().flatten() {
+        if !beacons.contains(&beacon) {
+            beacons.push(beacon);
+        }
+    }
+    let distance = scanners.iter().flat_map(|&a| scanners.iter().map(move |&b| (b - a).abs())).max().unwrap();
+    let mut reports: Vec<(Vector, Vec<Vector>)> = scanners.into_iter().zip(seen).collect();
+    rng.shuffle(&mut reports);
+    let mut text = String::new();
+    for (k, (position, beacons)) in reports.iter().enumerate() {
+        let turns = rng.size(0..=23);
+        let rotation = Matrix::rotation_walk().take(turns).fold(Matrix::default(), |a, r| r * a);
+        text += &format!("--- scanner {} ---\n", k);
+        for &beacon in beacons {
+            let Vector { x, y, z } = rotation * (beacon - *position);
+            text += &format!("{},{},{}\n", x, y, z);
+        }
+        text.push('\n');
+    }
+    Generated::answered(text, beacons.len(), distance)
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day19;
+
+impl aoc_common::Solution for Day19 {
+    type Input = Survey;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = isize;
+
+    fn parse(input: &str) -> Result<Survey, Error> {
+        Survey::read(input)
+    }
+
+    fn part1(survey: &Survey) -> Result<usize, Error> {
+        Ok(survey.beacons().len())
+    }
+
+    fn part2(survey: &Survey) -> Result<isize, Error> {
+        Ok(survey.max_distance())
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+}
@@ -1,16 +1,19 @@
 use crate::vector::Vector;
 
 
+/// One row of a matrix.
+pub type Row = (isize,isize,isize);
+
+
+/// A 3x3 integer matrix, used for rotations.
 #[derive(Eq,PartialEq,Copy,Clone,Debug,Hash)]
 pub struct Matrix {
-    pub entries: (
-        (isize,isize,isize),
-        (isize,isize,isize),
-        (isize,isize,isize),
-    )
+    /// The rows, top to bottom.
+    pub entries: (Row, Row, Row)
 }
 
 
+/// The entries of a matrix, row by row.
 pub struct MatrixIterator<'a> {
     offset: usize,
     matrix: &'a Matrix,
@@ -41,8 +44,10 @@ impl<'a> Iterator for MatrixIterator<'a> {
 }
 
 
-impl<'a> Matrix {
+impl Matrix {
 
+    /// Rotations which, multiplied up one after another, visit each of the 24
+    /// orientations of a cube once.
     pub fn rotation_walk() -> RotationWalk {
         RotationWalk::default()
     }
@@ -123,6 +128,7 @@ impl std::ops::Mul for Matrix {
     }
 }
 
+/// The walk behind [`Matrix::rotation_walk`].
 #[derive(Default)]
 pub struct RotationWalk {
     _swap: bool,
@@ -150,7 +156,7 @@ impl Iterator for RotationWalk {
         } else {
             let step = self._step;
             self._step += 1;
-            Some(if step % 4 == 0 {
+            Some(if step.is_multiple_of(4) {
                 Matrix::roll()
             } else {
                 Matrix::turn()
@@ -1,19 +1,25 @@
 use crate::matrix::Matrix;
 
 
+/// A position or offset in three dimensions.
 #[derive(Clone,Copy,Debug,Eq,PartialEq,Hash)]
 pub struct Vector {
+    /// The x coordinate.
     pub x: isize,
+    /// The y coordinate.
     pub y: isize,
+    /// The z coordinate.
     pub z: isize,
 }
 
 
 impl Vector {
+    /// The Manhattan length.
     pub fn abs(&self) -> isize {
-        return self.x.abs() + self.y.abs() + self.z.abs()
+        self.x.abs() + self.y.abs() + self.z.abs()
     }
 
+    /// A rotation taking this vector to `other`, if any.
     pub fn rotates_into(&self, other: &Vector) -> Option<Matrix> {
         let mut a = Matrix::default();
         let w = *self;
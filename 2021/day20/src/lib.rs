@@ -0,0 +1,216 @@
+//! Advent of Code 2021, day 20: Trench Map.
+
+#![warn(missing_docs)]
+
+use std::fmt;
+
+use aoc_common::{Class, Generated, Grid, GridError, Image, RenderFrame, Rng, Span, ToError};
+use aoc_common::animation::{self, Cell};
+
+/// Why the trench map cannot be read.
+#[derive(Debug)]
+pub enum Error {
+    /// There is no enhancement algorithm, or no image after it.
+    InvalidInputFormat,
+    /// The algorithm has this many characters rather than 512.
+    InvalidAlgorithmLength(usize, Span),
+    /// A character of the algorithm that is neither `#` nor `.`.
+    InvalidCharacterInAlgorithm(char, Span),
+    /// A pixel of the image that is neither `#` nor `.`.
+    InvalidCharacterInImage(char, Span),
+    /// A row that is shorter or longer than the first.
+    RaggedImage(Span),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidInputFormat => write!(f, "Input is empty."),
+            Error::InvalidAlgorithmLength(n, _) => write!(f, "Enhancement algorithm has {} characters instead of 512.", n),
+            Error::InvalidCharacterInAlgorithm(c, _) => write!(f, "Expected `#` or `.`, found `{}`.", c),
+            Error::InvalidCharacterInImage(c, _) => write!(f, "Expected `#` or `.` in the image, found `{}`.", c),
+            Error::RaggedImage(_) => write!(f, "All rows of the image must have the same length."),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::InvalidInputFormat => Class::Parse,
+            Error::InvalidAlgorithmLength(..) => Class::Parse,
+            Error::InvalidCharacterInAlgorithm(..) => Class::Parse,
+            Error::InvalidCharacterInImage(..) => Class::Parse,
+            Error::RaggedImage(..) => Class::Parse,
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::InvalidAlgorithmLength(_, span) => Some(*span),
+            Error::InvalidCharacterInAlgorithm(_, span) => Some(*span),
+            Error::InvalidCharacterInImage(_, span) => Some(*span),
+            Error::RaggedImage(span) => Some(*span),
+            _ => None,
+        }
+    }
+}
+
+/// The image with the enhancement algorithm to apply to it.
+#[derive(Clone)]
+pub struct EnhanceableImage {
+    algorithm: Vec<bool>,
+    image: Grid<bool>,
+    background: bool,
+}
+
+impl EnhanceableImage {
+    fn is_pixel_lit(&self, x: isize, y: isize) -> bool {
+        if x < 0 || y < 0 {
+            return self.background;
+        }
+        self.image.get((x as usize, y as usize)).copied().unwrap_or(self.background)
+    }
+
+    fn is_enhanced_pixel_lit(&self, x: isize, y: isize) -> bool {
+        let mut index: usize = 0;
+        for dy in [-1,0,1] {
+            for dx in [-1,0,1] {
+                index <<= 1;
+                if self.is_pixel_lit(x+dx, y+dy) {
+                    index |= 1;
+                }
+            }
+        }
+        self.algorithm[index]
+    }
+
+    fn enhance(&mut self) {
+        let image = Grid::from_fn(self.image.width() + 2, self.image.height() + 2, |(x, y)| {
+            self.is_enhanced_pixel_lit(x as isize - 1, y as isize - 1)
+        });
+        self.background = self.algorithm[if self.background { 0b1_1111_1111 } else { 0 }];
+        self.image = image;
+    }
+
+    fn parse(text: &str) -> Result<Self,Error> {
+        let lines = aoc_common::trimmed(text);
+        let (&program, rows) = lines.split_first()
+            .check(Error::InvalidInputFormat)?;
+        if program.len() != 0b1_000_000_000 {
+            return Err(Error::InvalidAlgorithmLength(program.len(), Span::of(text, program)));
+        }
+
+        let algorithm = program.char_indices().map(|(k, c)| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+             _  => Err(Error::InvalidCharacterInAlgorithm(c, Span::at(text, program, k)))
+        }).collect::<Result<Vec<_>,_>>()?;
+
+        let image = Grid::from_rows(text, rows, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+             _  => None
+        }).map_err(|e| match e {
+            GridError::InvalidCharacter(c, span) => Error::InvalidCharacterInImage(c, span),
+            GridError::RaggedRow(span) => Error::RaggedImage(span),
+            GridError::Empty => Error::InvalidInputFormat,
+        })?;
+        Ok(EnhanceableImage {algorithm, image, background: false })
+    }
+}
+
+impl fmt::Display for EnhanceableImage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.image.render(|&lit| if lit {'#'} else {'.'}) {
+            writeln!(f, "{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+
+fn enhanced(img: &EnhanceableImage, rounds: usize) -> EnhanceableImage {
+    let mut img = img.clone();
+    for _ in 0..rounds {
+        img.enhance();
+    }
+    img
+}
+
+fn lit_after(img: &EnhanceableImage, rounds: usize) -> usize {
+    enhanced(img, rounds).image.cells().filter(|&&lit| lit).count()
+}
+
+/// The image enhanced round by round.
+struct Enhancement {
+    img: EnhanceableImage,
+    rounds: usize,
+}
+
+impl RenderFrame for Enhancement {
+    fn frame(&self) -> Grid<Cell> {
+        self.img.image.map(|&lit| if lit { Cell::coloured('#', animation::WHITE) } else { Cell::coloured('.', animation::grey(1, 4)) })
+    }
+
+    fn step(&mut self) -> bool {
+        if self.rounds == 0 {
+            return false;
+        }
+        self.img.enhance();
+        self.rounds -= 1;
+        true
+    }
+}
+
+/// An algorithm that never lights the whole infinite image for good: if it
+/// lights dark surroundings, it darkens lit ones again.
+fn random_input(rng: &mut Rng) -> Generated {
+    let pixel = |rng: &mut Rng| if rng.chance(0.5) { '#' } else { '.' };
+    let mut algorithm: Vec<char> = (0..0b1_000_000_000).map(|_| pixel(rng)).collect();
+    if algorithm[0] == '#' {
+        algorithm[0b111_111_111] = '.';
+    }
+    let mut text: String = algorithm.into_iter().collect();
+    text += "\n\n";
+    let width = rng.size(1..=20);
+    for _ in 0..rng.size(1..=20) {
+        text.extend((0..width).map(|_| pixel(rng)));
+        text.push('\n');
+    }
+    Generated::text(text)
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day20;
+
+impl aoc_common::Solution for Day20 {
+    type Input = EnhanceableImage;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<EnhanceableImage, Error> {
+        EnhanceableImage::parse(input)
+    }
+
+    fn part1(img: &EnhanceableImage) -> Result<usize, Error> {
+        Ok(lit_after(img, 2))
+    }
+
+    fn part2(img: &EnhanceableImage) -> Result<usize, Error> {
+        Ok(lit_after(img, 50))
+    }
+
+    fn render(img: &EnhanceableImage) -> Result<Option<Image>, Error> {
+        Ok(Some(Image::bitmap(&enhanced(img, 50).image)))
+    }
+
+    fn animate(img: &EnhanceableImage) -> Option<Box<dyn RenderFrame>> {
+        Some(Box::new(Enhancement { img: img.clone(), rounds: 50 }))
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+}
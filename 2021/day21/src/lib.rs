@@ -0,0 +1,219 @@
+//! Advent of Code 2021, day 21: Dirac Dice.
+
+#![warn(missing_docs)]
+
+use std::fmt;
+
+use aoc_common::{Class, Generated, Rng, Span, ToError};
+
+/// Why the Dirac Dice game cannot be played.
+#[derive(Debug)]
+pub enum Error {
+    /// The deterministic die has no more rolls.
+    BrokenDie,
+    /// A player moved after the game had been won.
+    GameEnded,
+    /// A line whose starting position is not a number.
+    InvalidStartingPosition(String, Span),
+    /// The game needs exactly two players, not this many.
+    InvalidPlayerCount(usize),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::BrokenDie => write!(f, "The die ran out of numbers."),
+            Error::GameEnded => write!(f, "The game has already ended."),
+            Error::InvalidStartingPosition(line, _) => write!(f, "Expected a starting position like `Player 1 starting position: 4`, got `{}`.", line),
+            Error::InvalidPlayerCount(n) => write!(f, "Expected two players, found {}.", n),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::BrokenDie => Class::Solve,
+            Error::GameEnded => Class::Solve,
+            Error::InvalidStartingPosition(..) => Class::Parse,
+            Error::InvalidPlayerCount(..) => Class::Parse,
+        }
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::InvalidStartingPosition(_, span) => Some(*span),
+            _ => None,
+        }
+    }
+}
+
+trait TakeExactly<'a, I> where I: Iterator {
+    fn take_exactly(&'a mut self, n: usize) -> TakeExactlyIterator<'a, I>;
+}
+
+struct TakeExactlyIterator<'a, I> where I: Iterator {
+    iter: &'a mut I,
+    done: usize,
+    take: usize,
+}
+
+impl<'a, I, T> Iterator for TakeExactlyIterator<'a, I> where I: Iterator<Item=T> {
+    type Item = Option<T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let done = self.done;
+        if done >= self.take {
+            None
+        } else {
+            self.done += 1;
+            Some(self.iter.next())
+        }
+    }
+}
+
+impl<'a, I> TakeExactly<'a, I> for I where I: Iterator {
+    fn take_exactly(&'a mut self, n: usize) -> TakeExactlyIterator<'a, I> {
+        TakeExactlyIterator{iter:self, done:0, take:n}
+    }
+}
+
+#[derive(Copy,Clone,Debug)]
+struct Player {
+    score: usize,
+    field: usize,
+}
+
+struct DiracGame<I> where I: Iterator<Item=usize> {
+    round: usize,
+    players: Vec<Player>,
+    die: Option<I>,
+    max: usize,
+}
+
+impl<I> DiracGame<I> where I: Iterator<Item=usize> {
+
+    fn has_ended(&self) -> bool {
+        self.die.is_none()
+    }
+
+    fn next_player(&self) -> Player {
+        self.players[self.round % self.players.len()]
+    }
+
+    fn play_round(&mut self) -> Result<&Self,Error> {
+        let score = match &mut self.die {
+            None => Err(Error::GameEnded),
+            Some(die) => die.take_exactly(3).sum::<Option<usize>>()
+                .check(Error::BrokenDie),
+        }?;
+        let round = self.round;
+        let index = round % self.players.len();
+        let player = &mut self.players[index];
+        player.field = ((player.field + score - 1) % 10) + 1;
+        player.score += player.field;
+        self.round += 1;
+        if player.score >= self.max {
+            self.die = None;
+        }
+        Ok(self)
+    }
+
+    fn new(starting_positions: &[usize], die: I, max: usize) -> Self {
+        let players = starting_positions.iter().map(|&p| Player{score:0, field:p}).collect();
+        DiracGame{die:Some(die), players, round:0, max}
+    }
+
+}
+
+fn quantum_game(
+    p1: &Player,
+    p2: &Player,
+) -> (usize, usize) {
+    if p2.score >= 21 {
+        (0, 1)
+    } else {
+        [(3,1), (4,3), (5,6), (6,7), (7,6), (8,3), (9,1)]
+            .iter()
+            .map(|(score, count)| {
+                let field = ((p1.field + score - 1) % 10) + 1;
+                let score = p1.score + field;
+                let (w1, w2) = quantum_game(p2, &Player{field, score});
+                (w2 * count, w1 * count)
+            })
+            .reduce(|(w1, w2), (u1, u2)| (w1 + u1, w2 + u2))
+            .unwrap()
+    }
+}
+
+fn random_input(rng: &mut Rng) -> Generated {
+    let (a, b) = (rng.range(1..=10), rng.range(1..=10));
+    Generated::text(format!("Player 1 starting position: {}\nPlayer 2 starting position: {}\n", a, b))
+}
+
+/// Solves both parts of the puzzle.
+pub struct Day21;
+
+impl aoc_common::Solution for Day21 {
+    type Input = Vec<usize>;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<usize>, Error> {
+        let positions: Vec<usize> = aoc_common::trimmed(input)
+            .iter()
+            .map(|line| {
+                let position = line.rsplit(':').next().unwrap_or(line).trim();
+                position.parse().check(Error::InvalidStartingPosition(line.to_string(), Span::of(input, position)))
+            })
+            .collect::<Result<_,_>>()?;
+        if positions.len() != 2 {
+            return Err(Error::InvalidPlayerCount(positions.len()));
+        }
+        Ok(positions)
+    }
+
+    fn part1(positions: &Vec<usize>) -> Result<usize, Error> {
+        let mut game = DiracGame::new(positions, 1.., 1000);
+        while !game.has_ended() {
+            game.play_round()?;
+        }
+        Ok(game.next_player().score * game.round * 3)
+    }
+
+    fn part2(positions: &Vec<usize>) -> Result<usize, Error> {
+        let (w1, w2) = quantum_game(
+            &Player{field:positions[0], score:0},
+            &Player{field:positions[1], score:0});
+        Ok(std::cmp::max(w1, w2))
+    }
+
+    fn generate(rng: &mut Rng) -> Option<Generated> {
+        Some(random_input(rng))
+    }
+}
+
+
+#[test]
+fn example_part1() {
+    let mut d = DiracGame::new(&[4,8], 1.., 1000);
+    for _ in 0..330 {
+        let r = d.play_round();
+        assert!(r.is_ok());
+        let r = r.unwrap();
+        assert!(!r.has_ended());
+    }
+    assert!(d.play_round().unwrap().has_ended());
+    assert_eq!(d.round, 331);
+    assert_eq!(d.players[1].score, 745);
+}
+
+#[test]
+fn example_part2() {
+    assert_eq!(
+        quantum_game(
+            &Player{field:4,score:0},
+            &Player{field:8,score:0},
+        ), (444356092776315, 341960390180808));
+}
+
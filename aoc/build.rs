@@ -0,0 +1,44 @@
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc_common::fixture::fixtures;
+
+/// Writes one test per fixture in `<year>/dayNN/fixtures` of the workspace
+/// to `$OUT_DIR/fixtures.rs`; see `src/fixtures.rs`.
+fn main() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf();
+    let mut tests = String::new();
+    for (year, dir) in numbered(&root, "") {
+        println!("cargo:rerun-if-changed={}", dir.display());
+        for (day, dir) in numbered(&dir, "day") {
+            let fixture_dir = dir.join("fixtures");
+            println!("cargo:rerun-if-changed={}", dir.display());
+            println!("cargo:rerun-if-changed={}", fixture_dir.display());
+            for (name, path) in fixtures(&fixture_dir) {
+                println!("cargo:rerun-if-changed={}", path.display());
+                println!("cargo:rerun-if-changed={}", path.with_extension("answers").display());
+                let name: String = name.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+                let _ = write!(tests, "\n#[test]\nfn y{}_day{:02}_{}() {{\n    check({}, {}, {:?});\n}}\n",
+                    year, day, name, year, day, path.display().to_string());
+            }
+        }
+    }
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("fixtures.rs");
+    fs::write(out, tests).unwrap();
+}
+
+/// The subdirectories of `dir` named `<prefix><number>`, by number.
+fn numbered(dir: &Path, prefix: &str) -> Vec<(u16, PathBuf)> {
+    let mut result: Vec<(u16, PathBuf)> = fs::read_dir(dir).into_iter().flatten()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            Some((name.strip_prefix(prefix)?.parse().ok()?, entry.path()))
+        })
+        .collect();
+    result.sort();
+    result
+}
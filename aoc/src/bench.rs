@@ -0,0 +1,173 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tracing::info_span;
+
+use aoc_common::{Answer, Solution};
+
+/// Wall-clock times of every iteration, per stage.
+#[derive(Debug, Clone, Default)]
+pub struct Timings {
+    pub parse: Vec<Duration>,
+    pub part1: Vec<Duration>,
+    pub part2: Vec<Duration>,
+}
+
+/// Summary of one stage, in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Stats {
+    pub min: u64,
+    pub median: u64,
+    pub max: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Stages {
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+/// The timings of a day, or why it was skipped.
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub year: u16,
+    pub day: u8,
+    pub iterations: usize,
+    #[serde(flatten)]
+    pub stages: Option<Stages>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped: Option<String>,
+}
+
+fn timed<T>(times: &mut Vec<Duration>, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = black_box(f());
+    times.push(start.elapsed());
+    result
+}
+
+/// An answer with the time it took, parsing included.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solved {
+    pub answer: Answer,
+    pub elapsed: Duration,
+}
+
+/// Solves both parts once, timing each of them.
+pub fn solve<S: Solution>(text: &str) -> Result<[Solved; 2], S::Error> {
+    let mut times = Vec::new();
+    let input = timed(&mut times, || info_span!("parse").in_scope(|| S::parse(text)))?;
+    let part1 = timed(&mut times, || info_span!("part1").in_scope(|| S::part1(&input)))?.into();
+    let part2 = timed(&mut times, || info_span!("part2").in_scope(|| S::part2(&input)))?.into();
+    Ok([
+        Solved { answer: part1, elapsed: times[0] + times[1] },
+        Solved { answer: part2, elapsed: times[0] + times[2] },
+    ])
+}
+
+/// Runs every stage of a solution `iterations` times on the same text.
+pub fn measure<S: Solution>(text: &str, iterations: usize) -> Result<Timings, S::Error> {
+    let mut timings = Timings::default();
+    for _ in 0..iterations {
+        let input = timed(&mut timings.parse, || info_span!("parse").in_scope(|| S::parse(black_box(text))))?;
+        timed(&mut timings.part1, || info_span!("part1").in_scope(|| S::part1(&input)))?;
+        timed(&mut timings.part2, || info_span!("part2").in_scope(|| S::part2(&input)))?;
+    }
+    Ok(timings)
+}
+
+impl Stats {
+    pub fn new(times: &[Duration]) -> Stats {
+        let mut nanos: Vec<u64> = times.iter().map(|t| t.as_nanos() as u64).collect();
+        nanos.sort_unstable();
+        Stats {
+            min: nanos.first().copied().unwrap_or(0),
+            median: nanos.get(nanos.len() / 2).copied().unwrap_or(0),
+            max: nanos.last().copied().unwrap_or(0),
+        }
+    }
+}
+
+impl Report {
+    pub fn new(year: u16, day: u8, timings: &Timings) -> Report {
+        Report {
+            year,
+            day,
+            iterations: timings.parse.len(),
+            stages: Some(Stages {
+                parse: Stats::new(&timings.parse),
+                part1: Stats::new(&timings.part1),
+                part2: Stats::new(&timings.part2),
+            }),
+            skipped: None,
+        }
+    }
+
+    pub fn skipped(year: u16, day: u8, reason: &str) -> Report {
+        Report { year, day, iterations: 0, stages: None, skipped: Some(reason.to_string()) }
+    }
+}
+
+fn human(nanos: u64) -> String {
+    match nanos {
+        n if n < 1_000 => format!("{}ns", n),
+        n if n < 1_000_000 => format!("{:.1}µs", n as f64 / 1e3),
+        n if n < 1_000_000_000 => format!("{:.1}ms", n as f64 / 1e6),
+        n => format!("{:.2}s", n as f64 / 1e9),
+    }
+}
+
+/// The wall time of every day, slowest first, then the time all of them
+/// took together on `jobs` threads and the sum of their times.
+pub fn wall_table(walls: &[(u16, u8, Duration)], total: Duration, jobs: usize) -> Vec<String> {
+    let mut sorted = walls.to_vec();
+    sorted.sort_by(|a, b| b.2.cmp(&a.2).then((a.0, a.1).cmp(&(b.0, b.1))));
+    let mut lines = vec![format!("year  day  {:>9}", "wall")];
+    for (year, day, wall) in &sorted {
+        lines.push(format!("{:4}  {:3}  {:>9}", year, day, human(wall.as_nanos() as u64)));
+    }
+    let sum: Duration = walls.iter().map(|(_, _, wall)| *wall).sum();
+    lines.push(format!("total      {:>9}  (sum {}, {} job{})",
+        human(total.as_nanos() as u64), human(sum.as_nanos() as u64), jobs, if jobs == 1 { "" } else { "s" }));
+    lines
+}
+
+pub fn print_table(reports: &[Report]) {
+    println!("year  day  stage  {:>9}  {:>9}  {:>9}", "min", "median", "max");
+    for r in reports {
+        let Some(stages) = r.stages else {
+            println!("{:4}  {:3}  skipped, {}", r.year, r.day, r.skipped.as_deref().unwrap_or(""));
+            continue;
+        };
+        for (stage, stats) in [("parse", stages.parse), ("part1", stages.part1), ("part2", stages.part2)] {
+            println!("{:4}  {:3}  {:5}  {:>9}  {:>9}  {:>9}",
+                r.year, r.day, stage, human(stats.min), human(stats.median), human(stats.max));
+        }
+    }
+}
+
+#[test]
+fn stats_pick_min_median_max() {
+    let times: Vec<Duration> = [5, 1, 4, 2, 3].iter().map(|&n| Duration::from_nanos(n)).collect();
+    assert_eq!(Stats::new(&times), Stats { min: 1, median: 3, max: 5 });
+}
+
+#[test]
+fn skipped_days_are_reported_without_timings() {
+    let json = serde_json::to_string(&Report::skipped(2021, 3, "no input")).unwrap();
+    assert_eq!(json, r#"{"year":2021,"day":3,"iterations":0,"skipped":"no input"}"#);
+    let timings = Timings { parse: vec![Duration::from_nanos(2)], part1: vec![Duration::from_nanos(3)], part2: vec![Duration::from_nanos(4)] };
+    let json = serde_json::to_string(&Report::new(2021, 3, &timings)).unwrap();
+    assert!(json.contains(r#""iterations":1,"parse":{"min":2,"median":2,"max":2}"#));
+    assert!(!json.contains("skipped"));
+}
+
+#[test]
+fn walls_are_listed_slowest_first() {
+    let walls = [(2021, 1, Duration::from_millis(2)), (2020, 3, Duration::from_millis(9)), (2021, 2, Duration::from_millis(5))];
+    let lines = wall_table(&walls, Duration::from_millis(10), 2);
+    let days: Vec<&str> = lines[1..4].iter().map(|line| &line[..9]).collect();
+    assert_eq!(days, ["2020    3", "2021    2", "2021    1"]);
+}
@@ -0,0 +1,71 @@
+use aoc_common::{Failure, Generated, References, Rng, Solution};
+
+use crate::bench::{measure, solve, Solved, Timings};
+
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub solve: fn(&str) -> Result<[Solved; 2], Failure>,
+    pub bench: fn(&str, usize) -> Result<Timings, Failure>,
+    pub generate: fn(&mut Rng) -> Option<Generated>,
+    pub reference: fn(&str) -> Result<Option<References>, Failure>,
+    pub simplify: fn(&str) -> Vec<String>,
+    pub check: fn(&str, &str) -> Result<(), String>,
+}
+
+macro_rules! day {
+    ($year:literal, $day:literal, $solution:path) => {
+        Day {
+            year: $year,
+            day: $day,
+            solve: |text| {
+                let _day = tracing::info_span!("day", year = $year, day = $day).entered();
+                solve::<$solution>(text).map_err(|e| Failure::new(&e))
+            },
+            bench: |text, n| {
+                let _day = tracing::info_span!("day", year = $year, day = $day).entered();
+                measure::<$solution>(text, n).map_err(|e| Failure::new(&e))
+            },
+            generate: <$solution as Solution>::generate,
+            reference: |text| aoc_common::reference::<$solution>(text).map_err(|e| Failure::new(&e)),
+            simplify: <$solution as Solution>::simplify,
+            check: aoc_common::fixture::check::<$solution>,
+        }
+    };
+}
+
+pub const DAYS: &[Day] = &[
+    day!(2015, 1, aoc_2015_day01::Day01),
+    day!(2015, 2, aoc_2015_day02::Day02),
+    day!(2020, 1, aoc_2020_day01::Day01),
+    day!(2020, 2, aoc_2020_day02::Day02),
+    day!(2020, 3, aoc_2020_day03::Day03),
+    day!(2020, 4, aoc_2020_day04::Day04),
+    day!(2020, 5, aoc_2020_day05::Day05),
+    day!(2020, 6, aoc_2020_day06::Day06),
+    day!(2021, 1, aoc_2021_day01::Day01),
+    day!(2021, 2, aoc_2021_day02::Day02),
+    day!(2021, 3, aoc_2021_day03::Day03),
+    day!(2021, 4, aoc_2021_day04::Day04),
+    day!(2021, 5, aoc_2021_day05::Day05),
+    day!(2021, 6, aoc_2021_day06::Day06),
+    day!(2021, 7, aoc_2021_day07::Day07),
+    day!(2021, 8, aoc_2021_day08::Day08),
+    day!(2021, 9, aoc_2021_day09::Day09),
+    day!(2021, 10, aoc_2021_day10::Day10),
+    day!(2021, 11, aoc_2021_day11::Day11),
+    day!(2021, 12, aoc_2021_day12::Day12),
+    day!(2021, 13, aoc_2021_day13::Day13),
+    day!(2021, 14, aoc_2021_day14::Day14),
+    day!(2021, 15, aoc_2021_day15::Day15),
+    day!(2021, 16, aoc_2021_day16::Day16),
+    day!(2021, 17, aoc_2021_day17::Day17),
+    day!(2021, 18, aoc_2021_day18::Day18),
+    day!(2021, 19, aoc_2021_day19::Day19),
+    day!(2021, 20, aoc_2021_day20::Day20),
+    day!(2021, 21, aoc_2021_day21::Day21),
+];
+
+pub fn find(year: u16, day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.year == year && d.day == day)
+}
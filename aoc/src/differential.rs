@@ -0,0 +1,50 @@
+use aoc_common::Rng;
+
+use crate::days::Day;
+use crate::stress::Problem;
+
+/// Solves the inputs generated from `runs` consecutive seeds starting at
+/// `seed` with both the solver and the day's reference method, and describes
+/// each input they disagree on; `None` if the day cannot generate inputs or
+/// has no reference method.
+pub fn compare(day: &Day, seed: u64, runs: usize) -> Option<Vec<Problem>> {
+    let mut problems = Vec::new();
+    for seed in (seed..).take(runs) {
+        let generated = (day.generate)(&mut Rng::new(seed))?;
+        let problem = |message| Problem { seed, input: generated.text.clone(), message };
+        let references = match (day.reference)(&generated.text) {
+            Ok(references) => references?,
+            Err(failure) => {
+                problems.push(problem(failure.message));
+                continue;
+            },
+        };
+        let solved = match (day.solve)(&generated.text) {
+            Ok(solved) => solved,
+            Err(failure) => {
+                problems.push(problem(failure.message));
+                continue;
+            },
+        };
+        for (part, (solved, reference)) in solved.iter().zip(&references).enumerate() {
+            match reference {
+                Some(reference) if *reference != solved.answer => problems.push(problem(format!(
+                    "part {}: solver says {:?}, reference says {:?}", part + 1, solved.answer.to_string(), reference.to_string(),
+                ))),
+                _ => {},
+            }
+        }
+    }
+    Some(problems)
+}
+
+#[test]
+fn solvers_agree_with_their_references() {
+    let days = crate::days::DAYS;
+    let problems = crate::pool::map(days, days.len(), |day| compare(day, 0, 3).unwrap_or_default());
+    for (day, problems) in days.iter().zip(problems) {
+        if let Some(problem) = problems.first() {
+            panic!("{} day {:02}, seed {}: {}\n{}", day.year, day.day, problem.seed, problem.message, problem.input);
+        }
+    }
+}
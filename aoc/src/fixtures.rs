@@ -0,0 +1,17 @@
+use std::path::Path;
+
+use crate::days;
+
+/// Checks the answers of a day for one of its fixtures; the tests calling
+/// this are generated by `build.rs`.
+fn check(year: u16, day: u8, input: &str) {
+    let input = Path::new(input);
+    let solution = days::find(year, day).unwrap_or_else(|| panic!("{} day {} is not registered", year, day));
+    let text = aoc_common::fread(&input.to_string_lossy()).unwrap();
+    let expected = aoc_common::fread(&input.with_extension("answers").to_string_lossy()).unwrap();
+    if let Err(e) = (solution.check)(&text, &expected) {
+        panic!("{}:\n{}", input.display(), e);
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/fixtures.rs"));
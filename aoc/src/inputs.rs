@@ -0,0 +1,179 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Minimum time between two requests to the server.
+pub const INTERVAL: Duration = Duration::from_secs(5);
+
+/// Downloads puzzle inputs with a session token, at most one request per
+/// `interval`. The time of the last request is kept in `stamp` so the
+/// limit also holds across invocations.
+pub struct Fetcher {
+    pub base_url: String,
+    pub session: String,
+    pub interval: Duration,
+    pub stamp: PathBuf,
+}
+
+/// Resolves `(year, day)` to `<dir>/<year>/dayNN/input.txt`, fetching the
+/// file on a cache miss if a fetcher is configured.
+pub struct Inputs {
+    pub dir: PathBuf,
+    pub fetcher: Option<Fetcher>,
+}
+
+impl Fetcher {
+    fn wait(&self) {
+        let last = fs::read_to_string(&self.stamp).ok()
+            .and_then(|text| text.trim().parse().ok())
+            .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+        if let Some(last) = last {
+            let elapsed = SystemTime::now().duration_since(last).unwrap_or_default();
+            if elapsed < self.interval {
+                thread::sleep(self.interval - elapsed);
+            }
+        }
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        // Rounded up, so that the next wait is never cut short.
+        let _ = fs::write(&self.stamp, (now.as_millis() + 1).to_string());
+    }
+
+    pub fn url(&self, year: u16, day: u8) -> String {
+        format!("{}/{}/day/{}/input", self.base_url.trim_end_matches('/'), year, day)
+    }
+
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        self.wait();
+        ureq::request(method, url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", "aoc runner (ureq)")
+    }
+
+    /// Downloads the input, or describes why that failed.
+    pub fn fetch(&self, year: u16, day: u8) -> Result<String, String> {
+        let url = self.url(year, day);
+        let response = self.request("GET", &url).call().map_err(|e| e.to_string())?;
+        response.into_string().map_err(|e| format!("{}: {}", url, e))
+    }
+
+    /// Posts an answer and returns the page the server responds with.
+    pub fn post_answer(&self, year: u16, day: u8, part: usize, answer: &str) -> Result<String, String> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url.trim_end_matches('/'), year, day);
+        let response = self.request("POST", &url)
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|e| e.to_string())?;
+        response.into_string().map_err(|e| format!("{}: {}", url, e))
+    }
+}
+
+impl Inputs {
+    /// Configured by `AOC_INPUTS` (the cache directory, `root` by default),
+    /// `AOC_SESSION` (the session token; without it nothing is fetched) and
+    /// `AOC_BASE_URL`.
+    pub fn from_env(root: &Path) -> Inputs {
+        let dir = env::var_os("AOC_INPUTS").map(PathBuf::from).unwrap_or_else(|| root.to_path_buf());
+        let fetcher = env::var("AOC_SESSION").ok().map(|session| Fetcher {
+            base_url: env::var("AOC_BASE_URL").unwrap_or_else(|_| String::from(BASE_URL)),
+            session: session.trim().to_string(),
+            interval: INTERVAL,
+            stamp: dir.join(".last-fetch"),
+        });
+        Inputs { dir, fetcher }
+    }
+
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.dir.join(year.to_string()).join(format!("day{:02}", day)).join("input.txt")
+    }
+
+    /// The path of the cached input, fetched first if it is missing. Errors
+    /// describe why the input could not be fetched.
+    pub fn get(&self, year: u16, day: u8) -> Result<PathBuf, String> {
+        let path = self.path(year, day);
+        if path.exists() {
+            return Ok(path);
+        }
+        let fetcher = self.fetcher.as_ref()
+            .ok_or_else(|| format!("no input at {} and AOC_SESSION is not set", path.display()))?;
+        let text = fetcher.fetch(year, day)?;
+        let failed = |_| format!("failed to write {}", path.display());
+        fs::create_dir_all(path.parent().unwrap()).map_err(failed)?;
+        // Written aside and renamed into place, so that no reader ever sees
+        // a partial input and takes it for a cached one.
+        let partial = path.with_extension(format!("txt.{}.partial", std::process::id()));
+        fs::write(&partial, text).map_err(failed)?;
+        fs::rename(&partial, &path).map_err(|e| {
+            let _ = fs::remove_file(&partial);
+            failed(e)
+        })?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+pub fn temporary(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc-inputs-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[cfg(test)]
+pub fn inputs(dir: &Path, url: &str, interval: Duration) -> Inputs {
+    Inputs {
+        dir: dir.to_path_buf(),
+        fetcher: Some(Fetcher {
+            base_url: url.to_string(),
+            session: String::from("53cr3t"),
+            interval,
+            stamp: dir.join(".last-fetch"),
+        }),
+    }
+}
+
+#[test]
+fn cache_misses_are_fetched_once() {
+    let server = crate::mock::Server::start(|r| (200, format!("input of {}\n", r.line.split(' ').nth(1).unwrap())));
+    let dir = temporary("miss");
+    let inputs = inputs(&dir, &server.url, Duration::ZERO);
+    for _ in 0..2 {
+        let path = inputs.get(2021, 7).unwrap();
+        assert_eq!(path, dir.join("2021").join("day07").join("input.txt"));
+        assert_eq!(fs::read_to_string(path).unwrap(), "input of /2021/day/7/input\n");
+    }
+    let files = fs::read_dir(dir.join("2021").join("day07")).unwrap().count();
+    let requests = server.requests();
+    fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(files, 1);
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].line, "GET /2021/day/7/input HTTP/1.1");
+    assert_eq!(requests[0].cookie, "session=53cr3t");
+}
+
+#[test]
+fn failed_fetches_are_not_cached() {
+    let server = crate::mock::Server::start(|_| (404, String::from("Not found")));
+    let dir = temporary("failed");
+    let inputs = inputs(&dir, &server.url, Duration::ZERO);
+    let result = inputs.get(2021, 26);
+    let cached = inputs.path(2021, 26).exists();
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(result.unwrap_err().contains("404"));
+    assert!(!cached);
+}
+
+#[test]
+fn fetches_keep_their_distance() {
+    let server = crate::mock::Server::start(|_| (200, String::from("1\n")));
+    let dir = temporary("polite");
+    let inputs = inputs(&dir, &server.url, Duration::from_millis(300));
+    let start = std::time::Instant::now();
+    inputs.get(2021, 1).unwrap();
+    inputs.get(2021, 2).unwrap();
+    let elapsed = start.elapsed();
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(elapsed >= Duration::from_millis(300));
+}
@@ -0,0 +1,329 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::any::Any;
+use std::env;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::process::{self, Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, OnceLock};
+use std::thread;
+use std::time::Duration;
+
+use aoc_common::fixture::{escape, unescape};
+use aoc_common::{Answer, Class, Diagnostic, Failure, Span};
+
+use crate::bench::Solved;
+use crate::days::Day;
+
+/// The command that solves a day in a process of its own; not meant to be
+/// typed, so it is left out of the usage.
+pub const COMMAND: &str = "__solve";
+
+/// How long a day may run and how much heap it may hold at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    pub time: Duration,
+    pub memory: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Limits {
+        Limits { time: Duration::from_secs(60), memory: 4 << 30 }
+    }
+}
+
+/// Why a day stopped without answers or an error of its own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Stopped {
+    /// It was still running at the time limit.
+    Time(Duration),
+    /// It needed more heap than the memory limit.
+    Memory(usize),
+    /// It panicked, with the panic's message.
+    Panicked(String),
+    /// Its process ended some other way, with how it ended.
+    Crashed(String),
+}
+
+impl fmt::Display for Stopped {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Stopped::Time(limit) => write!(f, "Still running after the time limit of {:?}.", limit),
+            Stopped::Memory(limit) => write!(f, "Needed more than the memory limit of {} MiB.", limit >> 20),
+            Stopped::Panicked(message) => write!(f, "Panicked: {}", message),
+            Stopped::Crashed(how) => write!(f, "Stopped abnormally: {}", how),
+        }
+    }
+}
+
+impl Diagnostic for Stopped {
+    fn class(&self) -> Class {
+        match self {
+            Stopped::Time(_) | Stopped::Memory(_) => Class::Limit,
+            Stopped::Panicked(_) | Stopped::Crashed(_) => Class::Solve,
+        }
+    }
+}
+
+/// The message a panic was raised with, if it has one.
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload.downcast_ref::<&str>().map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_default()
+}
+
+/// Whether the heap is counted at all, the heap allocated since, and the
+/// most it may grow to.
+static ARMED: AtomicBool = AtomicBool::new(false);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static CEILING: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+/// The system allocator, counting the heap of the whole process whichever
+/// thread allocates or frees it once [`child`] arms it; until then it is
+/// the plain system allocator. A process that grows past its ceiling
+/// exits on the spot with the status of [`Class::Limit`], which tells its
+/// parent why; nothing waits on a lock, and returning null instead would
+/// abort it just like any other crash.
+pub struct Metered;
+
+fn charge(bytes: usize) {
+    if !ARMED.load(Ordering::Relaxed) {
+        return;
+    }
+    let live = LIVE.fetch_add(bytes, Ordering::Relaxed).saturating_add(bytes);
+    if live > CEILING.load(Ordering::Relaxed) {
+        // Exiting allocates too, which must not come back here.
+        ARMED.store(false, Ordering::Relaxed);
+        process::exit(Class::Limit.exit_code());
+    }
+}
+
+fn refund(bytes: usize) {
+    if ARMED.load(Ordering::Relaxed) {
+        // Blocks allocated before arming were never charged.
+        let _ = LIVE.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |live| Some(live.saturating_sub(bytes)));
+    }
+}
+
+unsafe impl GlobalAlloc for Metered {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        charge(layout.size());
+        let block = System.alloc(layout);
+        if block.is_null() {
+            refund(layout.size());
+        }
+        block
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        charge(layout.size());
+        let block = System.alloc_zeroed(layout);
+        if block.is_null() {
+            refund(layout.size());
+        }
+        block
+    }
+
+    unsafe fn realloc(&self, block: *mut u8, layout: Layout, size: usize) -> *mut u8 {
+        if size > layout.size() {
+            charge(size - layout.size());
+        }
+        let moved = System.realloc(block, layout, size);
+        match (moved.is_null(), size > layout.size()) {
+            (true, true) => refund(size - layout.size()),
+            (false, false) => refund(layout.size() - size),
+            _ => {},
+        }
+        moved
+    }
+
+    unsafe fn dealloc(&self, block: *mut u8, layout: Layout) {
+        System.dealloc(block, layout);
+        refund(layout.size());
+    }
+}
+
+/// The tracing options children are started with.
+static TRACE: OnceLock<Vec<String>> = OnceLock::new();
+
+/// Traces every child from now on with `args`.
+pub fn trace_children(args: Vec<String>) {
+    let _ = TRACE.set(args);
+}
+
+/// Solves a day for `text` in a process of its own: the process is killed
+/// once it has run for the time limit, and stops itself at the memory
+/// limit. A panic or crash fails the day like any other error.
+pub fn solve(day: &Day, text: &str, limits: Limits) -> Result<[Solved; 2], Failure> {
+    let exe = env::current_exe().map_err(|e| Failure::new(&Stopped::Crashed(e.to_string())))?;
+    let mut command = Command::new(exe);
+    command.args(TRACE.get().into_iter().flatten())
+        .args([COMMAND, &day.year.to_string(), &day.day.to_string(), &limits.memory.to_string()]);
+    let output = supervise(command, text, limits).map_err(|stopped| Failure::new(&stopped))?;
+    decode(&output).unwrap_or_else(|| Err(Failure::new(&Stopped::Crashed(String::from("no answers were written")))))
+}
+
+/// Runs `command` with `input` on its stdin and returns what it wrote on
+/// stdout if it succeeds.
+fn supervise(mut command: Command, input: &str, limits: Limits) -> Result<String, Stopped> {
+    let crashed = |e: io::Error| Stopped::Crashed(e.to_string());
+    let mut child = command.stdin(Stdio::piped()).stdout(Stdio::piped()).spawn().map_err(crashed)?;
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let input = input.to_string();
+    // A child that stops early leaves the writer with a broken pipe.
+    let writer = thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut output = String::new();
+        let _ = stdout.read_to_string(&mut output);
+        let _ = sender.send(output);
+    });
+    let output = match receiver.recv_timeout(limits.time) {
+        Ok(output) => output,
+        Err(_) => {
+            kill(&mut child);
+            return Err(Stopped::Time(limits.time));
+        },
+    };
+    let _ = writer.join();
+    let status = child.wait().map_err(crashed)?;
+    match status.code() {
+        Some(0) => Ok(output),
+        Some(code) if code == Class::Limit.exit_code() => Err(Stopped::Memory(limits.memory)),
+        _ => Err(Stopped::Crashed(status.to_string())),
+    }
+}
+
+fn kill(child: &mut Child) {
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// The body of a process started by [`solve`]: solves a day for the text
+/// on stdin within `memory` bytes and writes what it came to on stdout.
+pub fn child(day: &Day, memory: usize) -> io::Result<()> {
+    let mut text = String::new();
+    io::stdin().read_to_string(&mut text)?;
+    LIVE.store(0, Ordering::Relaxed);
+    CEILING.store(memory, Ordering::Relaxed);
+    ARMED.store(true, Ordering::Relaxed);
+    let solved = caught(day, &text);
+    ARMED.store(false, Ordering::Relaxed);
+    io::stdout().write_all(encode(&solved).as_bytes())
+}
+
+/// Solves a day, failing it if it panics.
+fn caught(day: &Day, text: &str) -> Result<[Solved; 2], Failure> {
+    panic::catch_unwind(AssertUnwindSafe(|| (day.solve)(text)))
+        .unwrap_or_else(|payload| Err(Failure::new(&Stopped::Panicked(panic_message(&*payload)))))
+}
+
+fn class(code: i32) -> Option<Class> {
+    [Class::Usage, Class::Input, Class::Parse, Class::Solve, Class::Limit].into_iter()
+        .find(|class| class.exit_code() == code)
+}
+
+/// What solving came to, as a child tells its parent: `solved` and a line
+/// per part with its time in nanoseconds, the kind of answer and the
+/// answer; or `failed` with the exit status of the class and the span,
+/// then the message. Values are escaped like fixture answers.
+fn encode(solved: &Result<[Solved; 2], Failure>) -> String {
+    match solved {
+        Ok(parts) => {
+            let mut text = String::from("solved\n");
+            for part in parts {
+                let (kind, value) = match &part.answer {
+                    Answer::Number(n) => ("number", n.to_string()),
+                    Answer::Text(s) => ("text", s.clone()),
+                    Answer::Art(rows) => ("art", rows.join("\n")),
+                };
+                text += &format!("{}\t{}\t{}\n", part.elapsed.as_nanos(), kind, escape(&value));
+            }
+            text
+        },
+        Err(failure) => {
+            let span = failure.span.map_or(String::from("-"), |s| format!("{}:{}:{}", s.line, s.column, s.length));
+            format!("failed\t{}\t{}\n{}\n", failure.class.exit_code(), span, escape(&failure.message))
+        },
+    }
+}
+
+fn decode(text: &str) -> Option<Result<[Solved; 2], Failure>> {
+    let mut lines = text.lines();
+    let head: Vec<&str> = lines.next()?.split('\t').collect();
+    match head[..] {
+        ["solved"] => {
+            let mut part = || -> Option<Solved> {
+                let mut fields = lines.next()?.splitn(3, '\t');
+                let elapsed = Duration::from_nanos(fields.next()?.parse().ok()?);
+                let kind = fields.next()?;
+                let value = unescape(fields.next()?);
+                let answer = match kind {
+                    "number" => Answer::Number(value.parse().ok()?),
+                    "text" => Answer::Text(value),
+                    "art" if value.is_empty() => Answer::Art(Vec::new()),
+                    "art" => Answer::art(value.split('\n')),
+                    _ => return None,
+                };
+                Some(Solved { answer, elapsed })
+            };
+            Some(Ok([part()?, part()?]))
+        },
+        ["failed", code, span] => {
+            let span = match span {
+                "-" => None,
+                _ => {
+                    let numbers: Vec<usize> = span.split(':').map(str::parse).collect::<Result<_, _>>().ok()?;
+                    let [line, column, length] = numbers[..] else { return None };
+                    Some(Span { line, column, length })
+                },
+            };
+            let class = class(code.parse().ok()?)?;
+            Some(Err(Failure { class, message: unescape(lines.next()?), span }))
+        },
+        _ => None,
+    }
+}
+
+#[test]
+fn outcomes_survive_the_trip_from_child_to_parent() {
+    let solved = Ok([
+        Solved { answer: Answer::Number(-(1 << 100)), elapsed: Duration::from_nanos(1234) },
+        Solved { answer: Answer::art(["#  #", "\\ \t"]), elapsed: Duration::ZERO },
+    ]);
+    assert_eq!(decode(&encode(&solved)), Some(solved));
+    let failed = Err(Failure { class: Class::Parse, message: String::from("Bad\nline"), span: Some(Span { line: 2, column: 3, length: 1 }) });
+    assert_eq!(decode(&encode(&failed)), Some(failed));
+    assert_eq!(decode("thread 'main' panicked"), None);
+}
+
+#[test]
+fn children_are_stopped_at_either_limit() {
+    let limits = Limits { time: Duration::from_millis(200), memory: 1 << 20 };
+    let shell = |script: &str| {
+        let mut command = Command::new("sh");
+        command.args(["-c", script]);
+        supervise(command, "input\n", limits)
+    };
+    assert_eq!(shell("cat"), Ok(String::from("input\n")));
+    assert_eq!(shell("sleep 5"), Err(Stopped::Time(limits.time)));
+    assert_eq!(shell("exit 6"), Err(Stopped::Memory(limits.memory)));
+    assert!(matches!(shell("kill -KILL $$"), Err(Stopped::Crashed(_))));
+}
+
+#[test]
+fn panicking_days_fail_like_any_other() {
+    let day = Day {
+        year: 2021,
+        day: 0,
+        solve: |_| panic!("out of bounds"),
+        bench: |_, _| unreachable!(),
+        generate: |_| None,
+        reference: |_| Ok(None),
+        simplify: |_| Vec::new(),
+        check: |_, _| Ok(()),
+    };
+    assert_eq!(caught(&day, ""), Err(Failure::new(&Stopped::Panicked(String::from("out of bounds")))));
+}
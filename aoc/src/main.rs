@@ -0,0 +1,606 @@
+mod bench;
+mod days;
+mod differential;
+#[cfg(test)]
+mod fixtures;
+mod inputs;
+mod limits;
+#[cfg(test)]
+mod mock;
+mod new;
+mod output;
+mod pool;
+mod shrink;
+mod stress;
+mod submit;
+mod trace;
+mod verify;
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use aoc_common::{Class, Failure, Rng};
+
+use bench::Solved;
+use days::{Day, DAYS};
+use inputs::Inputs;
+use limits::Limits;
+use output::{Format, Output};
+
+#[derive(Debug, Clone)]
+enum Error {
+    MissingArgument(&'static str),
+    InvalidArgument(String),
+    UnknownCommand(String),
+    UnknownDay(u16, u8),
+    DayFailed(Class),
+    /// How many days failed, and how many of those on a limit.
+    DaysFailed(usize, usize),
+    NoLimits(String),
+    AnswersFailed(usize),
+    WriteFailed(String),
+    FetchFailed(String),
+    SubmitFailed(String),
+    WrongAnswer,
+    DayExists(u16, u8),
+    CannotGenerate(u16, u8),
+    StressFailed(usize),
+    CannotCompare(u16, u8),
+    ComparisonFailed(usize),
+    NothingToShrink,
+}
+
+impl Error {
+    fn exit_code(&self) -> i32 {
+        match self {
+            Error::DayFailed(class) => class.exit_code(),
+            Error::DaysFailed(_, 0) => 1,
+            Error::DaysFailed(..) => Class::Limit.exit_code(),
+            Error::AnswersFailed(_) | Error::StressFailed(_) | Error::ComparisonFailed(_) => 1,
+            Error::SubmitFailed(_) | Error::WrongAnswer => 1,
+            Error::WriteFailed(_) | Error::FetchFailed(_) => Class::Input.exit_code(),
+            _ => Class::Usage.exit_code(),
+        }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: limits::Metered = limits::Metered;
+
+fn workspace() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+}
+
+fn default_input(day: &Day) -> PathBuf {
+    Inputs::from_env(workspace()).path(day.year, day.day)
+}
+
+fn number<T: std::str::FromStr>(arg: Option<String>, what: &'static str) -> Result<T, Error> {
+    let arg = arg.ok_or(Error::MissingArgument(what))?;
+    arg.parse().map_err(|_| Error::InvalidArgument(arg))
+}
+
+/// The text of an input and what solving a day for it came to.
+struct Attempt {
+    text: String,
+    solved: Result<[Solved; 2], Failure>,
+}
+
+/// Solves a day for one input within the limits; fails if the input cannot
+/// be read.
+fn attempt(day: &Day, file: &str, limits: Limits) -> Result<Attempt, Failure> {
+    let text = aoc_common::read(file).map_err(|e| Failure::new(&e))?;
+    let solved = limits::solve(day, &text, limits);
+    Ok(Attempt { text, solved })
+}
+
+/// Prints the answers of an attempt or reports why there are none; the
+/// heading names the input if `labelled`.
+fn show(output: &mut Output, day: &Day, file: &str, labelled: bool, attempt: Result<Attempt, Failure>) -> Result<(), Class> {
+    if labelled {
+        output.heading(&format!("== {} day {:02}: {} ==", day.year, day.day, aoc_common::label(file)));
+    } else {
+        output.heading(&format!("== {} day {:02} ==", day.year, day.day));
+    }
+    let attempt = attempt.map_err(|failure| failure.report("", ""))?;
+    let solved = attempt.solved
+        .map_err(|failure| failure.report(aoc_common::label(file), &attempt.text))?;
+    output.answers(day, aoc_common::label(file), &solved);
+    Ok(())
+}
+
+fn run(output: &mut Output, day: &Day, file: &str, labelled: bool, limits: Limits) -> Result<(), Class> {
+    show(output, day, file, labelled, attempt(day, file, limits))
+}
+
+/// Solves every day on `jobs` threads, then prints the results in the order
+/// of the days and a table of how long each took.
+fn run_all(output: &mut Output, jobs: usize, limits: Limits) -> Result<(), Error> {
+    let inputs = Inputs::from_env(workspace());
+    let mut tasks: Vec<(&'static Day, String)> = Vec::new();
+    let mut skipped: Vec<(&Day, String)> = Vec::new();
+    for day in DAYS {
+        match inputs.get(day.year, day.day) {
+            Ok(input) => tasks.push((day, input.to_string_lossy().into_owned())),
+            Err(e) => skipped.push((day, e)),
+        }
+    }
+
+    let start = Instant::now();
+    let attempts = pool::map(&tasks, jobs, |&(day, ref file)| {
+        let start = Instant::now();
+        let attempt = attempt(day, file, limits);
+        (attempt, start.elapsed())
+    });
+    let total = start.elapsed();
+
+    let mut failures = 0;
+    let mut limited = 0;
+    let mut walls: Vec<(u16, u8, Duration)> = Vec::new();
+    let mut attempts = tasks.iter().zip(attempts).peekable();
+    for day in DAYS {
+        if let Some((_, e)) = skipped.iter().find(|(d, _)| std::ptr::eq(*d, day)) {
+            output.heading(&format!("== {} day {:02} ==", day.year, day.day));
+            output.note(&format!("skipped, {}", e));
+            continue;
+        }
+        let Some(((_, file), (attempt, wall))) = attempts.next_if(|((d, _), _)| std::ptr::eq(*d, day)) else {
+            continue;
+        };
+        walls.push((day.year, day.day, wall));
+        if let Err(class) = show(output, day, file, false, attempt) {
+            failures += 1;
+            limited += usize::from(class == Class::Limit);
+        }
+    }
+    for line in bench::wall_table(&walls, total, jobs) {
+        output.note(&line);
+    }
+    match failures {
+        0 => Ok(()),
+        n => Err(Error::DaysFailed(n, limited)),
+    }
+}
+
+fn run_one(output: &mut Output, mut args: impl Iterator<Item = String>, limits: Limits) -> Result<(), Error> {
+    let year = number(args.next(), "year")?;
+    let day = number(args.next(), "day")?;
+    let day = days::find(year, day).ok_or(Error::UnknownDay(year, day))?;
+    let mut inputs = aoc_common::expand(args.collect());
+    if inputs.is_empty() {
+        let input = Inputs::from_env(workspace()).get(day.year, day.day).map_err(Error::FetchFailed)?;
+        inputs.push(input.to_string_lossy().into_owned());
+    }
+    let mut failed: Option<Class> = None;
+    for input in &inputs {
+        if let Err(class) = run(output, day, input, inputs.len() > 1, limits) {
+            failed = failed.or(Some(class));
+        }
+    }
+    failed.map_or(Ok(()), |class| Err(Error::DayFailed(class)))
+}
+
+fn run_command(mut args: impl Iterator<Item = String>) -> Result<(), Error> {
+    let mut format = Format::Text;
+    let mut all = false;
+    let mut jobs: usize = 1;
+    let mut limits = Limits::default();
+    let mut rest = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                let value = args.next().ok_or(Error::MissingArgument("format"))?;
+                format = value.parse().map_err(|_| Error::InvalidArgument(value))?;
+            },
+            "--all" => all = true,
+            "--jobs" => match number(args.next(), "jobs")? {
+                0 => return Err(Error::InvalidArgument(String::from("0"))),
+                n => jobs = n,
+            },
+            "--timeout" | "--memory" => limit(&arg, &mut args, &mut limits)?,
+            _ => rest.push(arg),
+        }
+    }
+    let mut output = Output::new(format);
+    let result = if all {
+        run_all(&mut output, jobs, limits)
+    } else {
+        run_one(&mut output, rest.into_iter(), limits)
+    };
+    output.finish();
+    result
+}
+
+/// Takes the value of `--timeout <seconds>` or `--memory <MiB>` into
+/// `limits`.
+fn limit(option: &str, args: &mut impl Iterator<Item = String>, limits: &mut Limits) -> Result<(), Error> {
+    match option {
+        "--timeout" => match number(args.next(), "timeout")? {
+            0 => return Err(Error::InvalidArgument(String::from("0"))),
+            n => limits.time = Duration::from_secs(n),
+        },
+        _ => match number::<usize>(args.next(), "memory")? {
+            0 => return Err(Error::InvalidArgument(String::from("0"))),
+            n => limits.memory = n.saturating_mul(1 << 20),
+        },
+    }
+    Ok(())
+}
+
+fn verify_all(mut args: impl Iterator<Item = String>) -> Result<(), Error> {
+    let mut limits = Limits::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--timeout" | "--memory" => limit(&arg, &mut args, &mut limits)?,
+            _ => return Err(Error::InvalidArgument(arg)),
+        }
+    }
+    let records: Vec<_> = DAYS.iter()
+        .flat_map(|day| verify::verify(day, &default_input(day), limits))
+        .collect();
+    verify::print_table(&records);
+    let failures = records.iter()
+        .filter(|r| matches!(r.status, verify::Status::Fail | verify::Status::Error))
+        .count();
+    match failures {
+        0 => Ok(()),
+        n => Err(Error::AnswersFailed(n)),
+    }
+}
+
+fn bench(mut args: impl Iterator<Item = String>) -> Result<(), Error> {
+    let mut iterations: usize = 10;
+    let mut json: Option<PathBuf> = None;
+    let mut days: Vec<&Day> = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--iterations" => match number(args.next(), "iterations")? {
+                0 => return Err(Error::InvalidArgument(String::from("0"))),
+                n => iterations = n,
+            },
+            "--json" => json = Some(PathBuf::from(args.next().ok_or(Error::MissingArgument("json"))?)),
+            "--timeout" | "--memory" => return Err(Error::NoLimits(arg)),
+            _ => {
+                let year = number(Some(arg), "year")?;
+                let day = number(args.next(), "day")?;
+                days.push(days::find(year, day).ok_or(Error::UnknownDay(year, day))?);
+            },
+        }
+    }
+    if days.is_empty() {
+        days = DAYS.iter().collect();
+    }
+
+    let mut reports = Vec::new();
+    let mut failures = 0;
+    for day in days {
+        let input = default_input(day);
+        let timings = match aoc_common::fread(&input.to_string_lossy()) {
+            Ok(text) => (day.bench)(&text, iterations),
+            Err(_) => {
+                reports.push(bench::Report::skipped(day.year, day.day, "no input"));
+                continue;
+            },
+        };
+        match timings {
+            Ok(timings) => reports.push(bench::Report::new(day.year, day.day, &timings)),
+            Err(e) => {
+                eprintln!("{} day {:02}: error: {}", day.year, day.day, e);
+                failures += 1;
+            },
+        }
+    }
+    bench::print_table(&reports);
+
+    if let Some(path) = json {
+        let text = serde_json::to_string_pretty(&reports).unwrap();
+        std::fs::write(&path, text + "\n")
+            .map_err(|_| Error::WriteFailed(path.display().to_string()))?;
+    }
+    match failures {
+        0 => Ok(()),
+        n => Err(Error::DaysFailed(n, 0)),
+    }
+}
+
+fn fetch(mut args: impl Iterator<Item = String>) -> Result<(), Error> {
+    let inputs = Inputs::from_env(workspace());
+    let days: Vec<&Day> = match args.next() {
+        Some(year) => {
+            let year = number(Some(year), "year")?;
+            let day = number(args.next(), "day")?;
+            vec![days::find(year, day).ok_or(Error::UnknownDay(year, day))?]
+        },
+        None => DAYS.iter().collect(),
+    };
+    for day in days {
+        let path = inputs.get(day.year, day.day).map_err(Error::FetchFailed)?;
+        println!("{} day {:02}: {}", day.year, day.day, path.display());
+    }
+    Ok(())
+}
+
+fn submit(mut args: impl Iterator<Item = String>) -> Result<(), Error> {
+    let year = number(args.next(), "year")?;
+    let day = number(args.next(), "day")?;
+    let part: usize = number(args.next(), "part")?;
+    let day = days::find(year, day).ok_or(Error::UnknownDay(year, day))?;
+    if !(1..=2).contains(&part) {
+        return Err(Error::InvalidArgument(part.to_string()));
+    }
+    let inputs = Inputs::from_env(workspace());
+    let file = inputs.get(day.year, day.day).map_err(Error::FetchFailed)?;
+    let file = file.to_string_lossy();
+    let text = aoc_common::fread(&file).map_err(|e| Error::DayFailed(Failure::new(&e).report("", "")))?;
+    let solved = (day.solve)(&text).map_err(|failure| Error::DayFailed(failure.report(&file, &text)))?;
+    let answer = solved[part - 1].answer.to_string();
+    let submitter = submit::Submitter { inputs: &inputs };
+    let verdict = submitter.submit(day.year, day.day, part, &answer).map_err(Error::SubmitFailed)?;
+    println!("{} day {:02} part {}: {} is {}", day.year, day.day, part, answer, verdict);
+    match verdict {
+        submit::Verdict::Right => Ok(()),
+        _ => Err(Error::WrongAnswer),
+    }
+}
+
+fn new_day(mut args: impl Iterator<Item = String>) -> Result<(), Error> {
+    let year: u16 = number(args.next(), "year")?;
+    let day: u8 = number(args.next(), "day")?;
+    if !(2015..=2099).contains(&year) {
+        return Err(Error::InvalidArgument(year.to_string()));
+    }
+    if !(1..=25).contains(&day) {
+        return Err(Error::InvalidArgument(day.to_string()));
+    }
+    let dir = workspace().join(year.to_string()).join(format!("day{:02}", day));
+    if days::find(year, day).is_some() || dir.exists() {
+        return Err(Error::DayExists(year, day));
+    }
+    new::scaffold(workspace(), year, day).map_err(Error::WriteFailed)?;
+    println!("Created {}", dir.display());
+    Ok(())
+}
+
+/// Prints a random input on stdout and the answers known for it on stderr,
+/// in the format of a fixture's `.answers` file.
+fn generate(mut args: impl Iterator<Item = String>) -> Result<(), Error> {
+    let year = number(args.next(), "year")?;
+    let day = number(args.next(), "day")?;
+    let day = days::find(year, day).ok_or(Error::UnknownDay(year, day))?;
+    let mut seed: u64 = 0;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => seed = number(args.next(), "seed")?,
+            _ => return Err(Error::InvalidArgument(arg)),
+        }
+    }
+    let generated = (day.generate)(&mut Rng::new(seed)).ok_or(Error::CannotGenerate(day.year, day.day))?;
+    print!("{}", generated.text);
+    for (part, answer) in generated.answers.iter().enumerate() {
+        if let Some(answer) = answer {
+            eprintln!("{}: {}", part + 1, aoc_common::fixture::escape(&answer.to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// The days named by `[<year> <day>]... [--seed N] [--runs N]`, or every
+/// day if none is named, with the first seed and the number of runs.
+struct Runs {
+    days: Vec<&'static Day>,
+    named: bool,
+    seed: u64,
+    runs: usize,
+}
+
+fn runs(mut args: impl Iterator<Item = String>) -> Result<Runs, Error> {
+    let mut seed: u64 = 0;
+    let mut runs: usize = 100;
+    let mut days: Vec<&Day> = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => seed = number(args.next(), "seed")?,
+            "--runs" => runs = number(args.next(), "runs")?,
+            "--timeout" | "--memory" => return Err(Error::NoLimits(arg)),
+            _ => {
+                let year = number(Some(arg), "year")?;
+                let day = number(args.next(), "day")?;
+                days.push(days::find(year, day).ok_or(Error::UnknownDay(year, day))?);
+            },
+        }
+    }
+    let named = !days.is_empty();
+    if !named {
+        days = DAYS.iter().collect();
+    }
+    Ok(Runs { days, named, seed, runs })
+}
+
+fn stress(args: impl Iterator<Item = String>) -> Result<(), Error> {
+    let Runs { days, named, seed, runs } = runs(args)?;
+    let mut failures = 0;
+    for day in days {
+        let Some(problems) = stress::stress(day, seed, runs) else {
+            if named {
+                return Err(Error::CannotGenerate(day.year, day.day));
+            }
+            continue;
+        };
+        for problem in &problems {
+            eprintln!("{} day {:02}, seed {}: {}", day.year, day.day, problem.seed, problem.message);
+        }
+        println!("{} day {:02}: {} of {} inputs failed", day.year, day.day, problems.len(), runs);
+        failures += problems.len();
+    }
+    match failures {
+        0 => Ok(()),
+        n => Err(Error::StressFailed(n)),
+    }
+}
+
+/// Shrinks an input that fails, or that the solver and the reference method
+/// disagree on, and writes what is left as a new fixture of the day.
+fn shrink(mut args: impl Iterator<Item = String>) -> Result<(), Error> {
+    let year = number(args.next(), "year")?;
+    let day = number(args.next(), "day")?;
+    let day = days::find(year, day).ok_or(Error::UnknownDay(year, day))?;
+    let file = args.next().ok_or(Error::MissingArgument("input"))?;
+    let mut name = String::from("shrunk");
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--name" => name = args.next().ok_or(Error::MissingArgument("name"))?,
+            _ => return Err(Error::InvalidArgument(arg)),
+        }
+    }
+    let text = aoc_common::read(&file).map_err(|e| Error::DayFailed(Failure::new(&e).report("", "")))?;
+    let symptom = shrink::diagnose(day, &text).ok_or(Error::NothingToShrink)?;
+    let shrunk = shrink::shrink(day, &text, &symptom);
+    let dir = workspace().join(day.year.to_string()).join(format!("day{:02}", day.day)).join("fixtures");
+    let path = (1..).map(|k| if k == 1 { name.clone() } else { format!("{}-{}", name, k) })
+        .map(|name| dir.join(name).with_extension("txt"))
+        .find(|path| !path.exists())
+        .unwrap();
+    let write = |path: &Path, text: &str| std::fs::create_dir_all(&dir)
+        .and_then(|_| std::fs::write(path, text))
+        .map_err(|_| Error::WriteFailed(path.display().to_string()));
+    write(&path, &shrunk)?;
+    write(&path.with_extension("answers"), &shrink::answers(day, &shrunk, &symptom))?;
+    println!("Input {}", symptom);
+    println!("Shrunk {} lines to {} in {}", text.lines().count(), shrunk.lines().count(), path.display());
+    if let shrink::Symptom::Panic(_) = symptom {
+        eprintln!("Fill in the expected answers in {}.", path.with_extension("answers").display());
+    }
+    Ok(())
+}
+
+/// Solves generated inputs with both the solver and the reference method
+/// and prints every input they disagree on.
+fn compare(args: impl Iterator<Item = String>) -> Result<(), Error> {
+    let Runs { days, named, seed, runs } = runs(args)?;
+    let mut failures = 0;
+    for day in days {
+        let Some(problems) = differential::compare(day, seed, runs) else {
+            if named {
+                return Err(Error::CannotCompare(day.year, day.day));
+            }
+            continue;
+        };
+        for problem in &problems {
+            eprintln!("{} day {:02}, seed {}: {}", day.year, day.day, problem.seed, problem.message);
+            for line in problem.input.lines() {
+                eprintln!("    {}", line);
+            }
+        }
+        println!("{} day {:02}: {} of {} inputs disagreed", day.year, day.day, problems.len(), runs);
+        failures += problems.len();
+    }
+    match failures {
+        0 => Ok(()),
+        n => Err(Error::ComparisonFailed(n)),
+    }
+}
+
+/// Solves a day in a process started by [`limits::solve`].
+fn solve_child(mut args: impl Iterator<Item = String>) -> Result<(), Error> {
+    let year = number(args.next(), "year")?;
+    let day = number(args.next(), "day")?;
+    let memory = number(args.next(), "memory")?;
+    let day = days::find(year, day).ok_or(Error::UnknownDay(year, day))?;
+    limits::child(day, memory).map_err(|_| Error::WriteFailed(String::from("-")))
+}
+
+fn main_or_error() -> Result<(), Error> {
+    let (trace, args) = trace::options(env::args().skip(1)).map_err(Error::MissingArgument)?;
+    let mut args = args.into_iter();
+    let command = args.next().ok_or(Error::MissingArgument("command"))?;
+    let _flame = trace.start(command == limits::COMMAND).map_err(Error::WriteFailed)?;
+    limits::trace_children(trace.args());
+    match command.as_str() {
+        limits::COMMAND => solve_child(args),
+        "run" => run_command(args),
+        "verify" => verify_all(args),
+        "bench" => bench(args),
+        "fetch" => fetch(args),
+        "submit" => submit(args),
+        "new" => new_day(args),
+        "generate" => generate(args),
+        "stress" => stress(args),
+        "compare" => compare(args),
+        "shrink" => shrink(args),
+        _ => Err(Error::UnknownCommand(command)),
+    }
+}
+
+fn main() {
+    let result = main_or_error();
+    match &result {
+        Ok(()) => {},
+        Err(Error::MissingArgument(what)) => {
+            eprintln!("Missing argument: {}", what);
+            eprintln!("Usage: aoc run [--format text|json|csv] [--timeout <seconds>] [--memory <MiB>] (<year> <day> [input|-]... | --all [--jobs N])");
+            eprintln!("       aoc verify [--timeout <seconds>] [--memory <MiB>]");
+            eprintln!("       aoc bench [<year> <day>] [--iterations N] [--json <path>]");
+            eprintln!("       aoc fetch [<year> <day>] | aoc submit <year> <day> <part>");
+            eprintln!("       aoc new <year> <day>");
+            eprintln!("       aoc generate <year> <day> [--seed N]");
+            eprintln!("       aoc stress [<year> <day>]... [--seed N] [--runs N]");
+            eprintln!("       aoc compare [<year> <day>]... [--seed N] [--runs N]");
+            eprintln!("       aoc shrink <year> <day> <input> [--name <fixture>]");
+            eprintln!("Any command also takes -v, -vv or -vvv to trace spans, and --flame <path> for a flame graph.");
+        },
+        Err(Error::InvalidArgument(arg)) => {
+            eprintln!("Invalid argument: {}", arg);
+        },
+        Err(Error::UnknownCommand(command)) => {
+            eprintln!("Unknown command: {}", command);
+        },
+        Err(Error::UnknownDay(year, day)) => {
+            eprintln!("No solution registered for {} day {}.", year, day);
+        },
+        Err(Error::DayFailed(_)) => {},
+        Err(Error::DaysFailed(n, 0)) => {
+            eprintln!("{} day(s) failed.", n);
+        },
+        Err(Error::DaysFailed(n, limited)) => {
+            eprintln!("{} day(s) failed, {} of them on a limit.", n, limited);
+        },
+        Err(Error::NoLimits(option)) => {
+            eprintln!("Option {} only applies to run and verify.", option);
+        },
+        Err(Error::AnswersFailed(n)) => {
+            eprintln!("{} answer(s) did not verify.", n);
+        },
+        Err(Error::WriteFailed(path)) => {
+            eprintln!("Failed to write to file: {}", path);
+        },
+        Err(Error::FetchFailed(e)) => {
+            eprintln!("Failed to fetch input: {}", e);
+        },
+        Err(Error::SubmitFailed(e)) => {
+            eprintln!("Not submitted: {}", e);
+        },
+        Err(Error::WrongAnswer) => {},
+        Err(Error::DayExists(year, day)) => {
+            eprintln!("{} day {} already exists.", year, day);
+        },
+        Err(Error::CannotGenerate(year, day)) => {
+            eprintln!("{} day {} cannot generate inputs.", year, day);
+        },
+        Err(Error::StressFailed(n)) => {
+            eprintln!("{} generated input(s) failed.", n);
+        },
+        Err(Error::CannotCompare(year, day)) => {
+            eprintln!("{} day {} has no reference method or cannot generate inputs.", year, day);
+        },
+        Err(Error::ComparisonFailed(n)) => {
+            eprintln!("{} generated input(s) were solved differently by the reference.", n);
+        },
+        Err(Error::NothingToShrink) => {
+            eprintln!("The input neither fails nor makes the solver disagree with the reference.");
+        },
+    }
+    if let Err(e) = result {
+        std::process::exit(e.exit_code());
+    }
+}
@@ -0,0 +1,60 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+#[derive(Debug, Clone, Default)]
+pub struct Request {
+    pub line: String,
+    pub cookie: String,
+    pub body: String,
+}
+
+/// A stand-in for the puzzle server: answers every request with the status
+/// and body `respond` picks for it and records what it was sent.
+pub struct Server {
+    pub url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl Server {
+    pub fn start(respond: fn(&Request) -> (u16, String)) -> Server {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&requests);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = match stream { Ok(s) => s, Err(_) => break };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = Request::default();
+                let mut length = 0;
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap_or(0) > 2 {
+                    let header = line.trim_end();
+                    let lower = header.to_ascii_lowercase();
+                    if request.line.is_empty() {
+                        request.line = header.to_string();
+                    } else if lower.starts_with("cookie:") {
+                        request.cookie = header[7..].trim().to_string();
+                    } else if lower.starts_with("content-length:") {
+                        length = header[15..].trim().parse().unwrap_or(0);
+                    }
+                    line.clear();
+                }
+                let mut body = vec![0; length];
+                let _ = reader.read_exact(&mut body);
+                request.body = String::from_utf8_lossy(&body).into_owned();
+                let (status, body) = respond(&request);
+                log.lock().unwrap().push(request);
+                let _ = write!(stream, "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status, body.len(), body);
+            }
+        });
+        Server { url, requests }
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
@@ -0,0 +1,101 @@
+use std::fs;
+use std::path::Path;
+
+use regex::Regex;
+
+const TEMPLATE: &str = include_str!("../../template.rs");
+
+/// Checks nothing until the example and its answers are filled in.
+const EXAMPLE_ANSWERS: &str = "# <part>: <answer>, one line per part\n";
+
+fn manifest(year: u16, day: u8) -> String {
+    format!(r#"[package]
+name = "aoc-{year}-day{day:02}"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+aoc-common = {{ path = "../../common" }}
+"#)
+}
+
+fn main_rs(year: u16, day: u8) -> String {
+    format!(r#"fn main() {{
+    aoc_common::main::<aoc_{year}_day{day:02}::Day{day:02}>();
+}}
+"#)
+}
+
+/// The (year, day) a line refers to, e.g. `"2021/day09",` or `day!(2021, 9, ...)`.
+fn key(line: &str) -> Option<(u16, u8)> {
+    let pattern = Regex::new(r"(20\d\d)\D{1,5}(\d{1,2})\b").unwrap();
+    let captures = pattern.captures(line)?;
+    Some((captures[1].parse().ok()?, captures[2].parse().ok()?))
+}
+
+/// Inserts `line` among the lines starting with `prefix`, keeping them
+/// ordered by year and day.
+fn insert_sorted(text: &str, prefix: &str, line: &str) -> Option<String> {
+    let new = key(line)?;
+    let lines: Vec<&str> = text.lines().collect();
+    let listed: Vec<usize> = (0..lines.len())
+        .filter(|&k| lines[k].starts_with(prefix) && key(lines[k]).is_some())
+        .collect();
+    let position = listed.iter()
+        .find(|&&k| key(lines[k]).unwrap() > new)
+        .copied()
+        .or_else(|| listed.last().map(|k| k + 1))?;
+    let mut result: Vec<&str> = lines[..position].to_vec();
+    result.push(line);
+    result.extend(&lines[position..]);
+    Some(result.join("\n") + "\n")
+}
+
+fn register(path: &Path, prefix: &str, line: &str) -> Result<(), String> {
+    let failed = || path.display().to_string();
+    let text = fs::read_to_string(path).map_err(|_| failed())?;
+    let text = insert_sorted(&text, prefix, line).ok_or_else(failed)?;
+    fs::write(path, text).map_err(|_| failed())
+}
+
+/// Creates the crate for a new day and registers it with the workspace
+/// and the runner. Returns the path that could not be written on failure.
+pub fn scaffold(root: &Path, year: u16, day: u8) -> Result<(), String> {
+    let dir = root.join(year.to_string()).join(format!("day{:02}", day));
+    let failed = |path: &Path| path.display().to_string();
+    fs::create_dir_all(dir.join("src")).map_err(|_| failed(&dir))?;
+    fs::create_dir_all(dir.join("fixtures")).map_err(|_| failed(&dir))?;
+
+    let lib = TEMPLATE.replace("DayNN", &format!("Day{:02}", day))
+        .replace("YEAR", &year.to_string())
+        .replace("DAY", &day.to_string());
+    for (name, content) in [
+        ("Cargo.toml", manifest(year, day)),
+        ("src/lib.rs", lib),
+        ("src/main.rs", main_rs(year, day)),
+        ("fixtures/example.txt", String::new()),
+        ("fixtures/example.answers", EXAMPLE_ANSWERS.to_string()),
+    ] {
+        let path = dir.join(name);
+        fs::write(&path, content).map_err(|_| failed(&path))?;
+    }
+
+    register(&root.join("Cargo.toml"), "    \"",
+        &format!("    \"{}/day{:02}\",", year, day))?;
+    register(&root.join("aoc/Cargo.toml"), "aoc-",
+        &format!("aoc-{year}-day{day:02} = {{ path = \"../{year}/day{day:02}\" }}"))?;
+    register(&root.join("aoc/src/days.rs"), "    day!(",
+        &format!("    day!({year}, {day}, aoc_{year}_day{day:02}::Day{day:02}),"))
+}
+
+#[test]
+fn registrations_stay_sorted() {
+    let members = "[workspace]\nmembers = [\n    \"aoc\",\n    \"2021/day09\",\n    \"2021/day10\",\n]\n";
+    assert_eq!(
+        insert_sorted(members, "    \"", "    \"2021/day11\",").unwrap(),
+        "[workspace]\nmembers = [\n    \"aoc\",\n    \"2021/day09\",\n    \"2021/day10\",\n    \"2021/day11\",\n]\n");
+    let days = "pub const DAYS: &[Day] = &[\n    day!(2021, 9, a::Day09),\n    day!(2021, 10, b::Day10),\n];\n";
+    assert_eq!(
+        insert_sorted(days, "    day!(", "    day!(2020, 1, c::Day01),").unwrap(),
+        "pub const DAYS: &[Day] = &[\n    day!(2020, 1, c::Day01),\n    day!(2021, 9, a::Day09),\n    day!(2021, 10, b::Day10),\n];\n");
+}
@@ -0,0 +1,122 @@
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::bench::Solved;
+use crate::days::Day;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(()),
+        }
+    }
+}
+
+/// One answer as emitted by `--format json|csv`; `elapsed` is in
+/// nanoseconds and includes parsing.
+#[derive(Debug, Clone, Serialize)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub input: String,
+    pub part: usize,
+    pub answer: String,
+    pub elapsed: u64,
+}
+
+/// Collects answers and prints them in the chosen format. Text is printed
+/// as it comes; JSON and CSV are printed as a whole by `finish`.
+pub struct Output {
+    format: Format,
+    records: Vec<Record>,
+}
+
+impl Output {
+    pub fn new(format: Format) -> Output {
+        Output { format, records: Vec::new() }
+    }
+
+    /// Prints a heading, which only belongs in text output.
+    pub fn heading(&self, text: &str) {
+        if self.format == Format::Text {
+            println!("{}", text);
+        }
+    }
+
+    /// Prints a remark; it goes to stderr unless the output is text.
+    pub fn note(&self, text: &str) {
+        match self.format {
+            Format::Text => println!("{}", text),
+            _ => eprintln!("{}", text),
+        }
+    }
+
+    pub fn answers(&mut self, day: &Day, input: &str, solved: &[Solved; 2]) {
+        for (k, s) in solved.iter().enumerate() {
+            match self.format {
+                Format::Text => aoc_common::print(k + 1, &s.answer),
+                _ => self.records.push(Record {
+                    year: day.year,
+                    day: day.day,
+                    input: input.to_string(),
+                    part: k + 1,
+                    answer: s.answer.to_string(),
+                    elapsed: s.elapsed.as_nanos() as u64,
+                }),
+            }
+        }
+    }
+
+    pub fn finish(self) {
+        match self.format {
+            Format::Text => {},
+            Format::Json => println!("{}", serde_json::to_string_pretty(&self.records).unwrap()),
+            Format::Csv => print!("{}", csv(&self.records)),
+        }
+    }
+}
+
+/// Quotes a field if it contains a separator, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn csv(records: &[Record]) -> String {
+    let mut result = String::from("year,day,input,part,answer,elapsed\n");
+    for r in records {
+        result += &format!("{},{},{},{},{},{}\n",
+            r.year, r.day, csv_field(&r.input), r.part, csv_field(&r.answer), r.elapsed);
+    }
+    result
+}
+
+#[test]
+fn csv_keeps_multi_line_answers_in_one_field() {
+    let record = Record {
+        year: 2021,
+        day: 13,
+        input: String::from("input.txt"),
+        part: 2,
+        answer: String::from("#\"#\n# #"),
+        elapsed: 42,
+    };
+    assert_eq!(csv(&[record]),
+        "year,day,input,part,answer,elapsed\n2021,13,input.txt,2,\"#\"\"#\n# #\",42\n");
+}
@@ -0,0 +1,33 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// Applies `f` to every item on up to `jobs` threads. Each thread takes the
+/// next item as soon as it is done with one; the results are in the order
+/// of the items, whatever order they finished in.
+pub fn map<T: Sync, R: Send>(items: &[T], jobs: usize, f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<R>>> = Mutex::new(items.iter().map(|_| None).collect());
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            scope.spawn(|| loop {
+                let k = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(k) else { break };
+                let result = f(item);
+                results.lock().unwrap()[k] = Some(result);
+            });
+        }
+    });
+    results.into_inner().unwrap().into_iter().map(|r| r.expect("every item was mapped")).collect()
+}
+
+#[test]
+fn results_keep_the_order_of_the_items() {
+    let items: Vec<u64> = (0..20).rev().collect();
+    let doubled = map(&items, 4, |&n| {
+        thread::sleep(std::time::Duration::from_millis(n));
+        2 * n
+    });
+    assert_eq!(doubled, items.iter().map(|n| 2 * n).collect::<Vec<_>>());
+    assert_eq!(map(&[] as &[u64], 4, |&n| n), Vec::<u64>::new());
+}
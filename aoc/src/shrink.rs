@@ -0,0 +1,112 @@
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::days::Day;
+use crate::limits::panic_message;
+
+/// What goes wrong when a day solves an input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Symptom {
+    Panic(String),
+    Failure(String),
+    /// The solver and the reference method disagree on a part.
+    Mismatch(usize),
+}
+
+impl fmt::Display for Symptom {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Symptom::Panic(message) => write!(f, "panics: {}", message),
+            Symptom::Failure(message) => write!(f, "fails: {}", message),
+            Symptom::Mismatch(part) => write!(f, "part {} differs from the reference", part),
+        }
+    }
+}
+
+/// What goes wrong with `text`, if anything; panics are caught.
+pub fn diagnose(day: &Day, text: &str) -> Option<Symptom> {
+    let diagnosis = panic::catch_unwind(AssertUnwindSafe(|| {
+        let solved = match (day.solve)(text) {
+            Ok(solved) => solved,
+            Err(failure) => return Some(Symptom::Failure(failure.message)),
+        };
+        let references = (day.reference)(text).ok().flatten()?;
+        solved.iter().zip(&references)
+            .position(|(solved, reference)| reference.as_ref().is_some_and(|r| *r != solved.answer))
+            .map(|part| Symptom::Mismatch(part + 1))
+    }));
+    diagnosis.unwrap_or_else(|payload| Some(Symptom::Panic(panic_message(&*payload))))
+}
+
+/// Cuts `text` down for as long as it keeps the `symptom`: whole lines by
+/// delta debugging first, then by the day's own simplifications, until
+/// neither helps. Panic messages are silenced meanwhile.
+pub fn shrink(day: &Day, text: &str, symptom: &Symptom) -> String {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let fails = |text: &str| diagnose(day, text).as_ref() == Some(symptom);
+    let mut text = text.to_string();
+    loop {
+        text = drop_lines(&text, &fails);
+        match (day.simplify)(&text).into_iter().find(|simpler| *simpler != text && fails(simpler)) {
+            Some(simpler) => text = simpler,
+            None => break,
+        }
+    }
+    panic::set_hook(hook);
+    text
+}
+
+fn join(lines: &[&str]) -> String {
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+/// Removes chunks of lines while the input still `fails`, halving the
+/// chunks whenever none can go.
+fn drop_lines(text: &str, fails: &impl Fn(&str) -> bool) -> String {
+    let mut lines: Vec<&str> = text.lines().collect();
+    let mut chunks = 2;
+    while lines.len() >= 2 {
+        let size = lines.len().div_ceil(chunks);
+        let rest = (0..lines.len()).step_by(size)
+            .map(|start| [&lines[..start], &lines[(start + size).min(lines.len())..]].concat())
+            .find(|rest| fails(&join(rest)));
+        match rest {
+            Some(rest) => {
+                lines = rest;
+                chunks = (chunks - 1).max(2);
+            },
+            None if size == 1 => break,
+            None => chunks = (chunks * 2).min(lines.len()),
+        }
+    }
+    join(&lines)
+}
+
+/// The `.answers` of a fixture for the shrunk `text`: the reference's
+/// answers for a mismatch, the failure for the parts it fails, and
+/// placeholders to fill in after a panic.
+pub fn answers(day: &Day, text: &str, symptom: &Symptom) -> String {
+    match symptom {
+        Symptom::Mismatch(_) => {
+            let references = (day.reference)(text).ok().flatten().unwrap_or_default();
+            references.iter().enumerate()
+                .filter_map(|(part, answer)| Some(format!("{}: {}\n", part + 1, aoc_common::fixture::escape(&answer.as_ref()?.to_string()))))
+                .collect()
+        },
+        Symptom::Failure(message) => {
+            let failed = |part| format!("{}: {}{}\n", part, aoc_common::fixture::FAILURE, aoc_common::fixture::escape(message));
+            [failed(1) + &failed(2), failed(1), failed(2)].into_iter()
+                .find(|answers| (day.check)(text, answers).is_ok())
+                .unwrap_or_else(|| failed(1) + &failed(2))
+        },
+        Symptom::Panic(message) => format!("# panicked: {}\n1: ?\n2: ?\n", message),
+    }
+}
+
+#[test]
+fn lines_are_dropped_until_the_failure_needs_all_that_remain() {
+    let fails = |text: &str| text.contains("b\n") && text.contains("e\n");
+    assert_eq!(drop_lines("a\nb\nc\nd\ne\nf\ng\n", &fails), "b\ne\n");
+    assert_eq!(drop_lines("e\nb\n", &fails), "e\nb\n");
+}
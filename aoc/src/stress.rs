@@ -0,0 +1,49 @@
+use aoc_common::Rng;
+
+use crate::days::Day;
+
+/// A generated input that failed, and how.
+pub struct Problem {
+    pub seed: u64,
+    pub input: String,
+    pub message: String,
+}
+
+/// Solves the inputs generated from `runs` consecutive seeds starting at
+/// `seed` and describes each one that fails or whose answers differ from
+/// the ones known by construction; `None` if the day cannot generate inputs.
+pub fn stress(day: &Day, seed: u64, runs: usize) -> Option<Vec<Problem>> {
+    let mut problems = Vec::new();
+    for seed in (seed..).take(runs) {
+        let generated = (day.generate)(&mut Rng::new(seed))?;
+        let solved = match (day.solve)(&generated.text) {
+            Ok(solved) => solved,
+            Err(failure) => {
+                problems.push(Problem { seed, input: generated.text, message: failure.message });
+                continue;
+            },
+        };
+        for (part, (solved, expected)) in solved.iter().zip(&generated.answers).enumerate() {
+            match expected {
+                Some(expected) if *expected != solved.answer => problems.push(Problem {
+                    seed,
+                    input: generated.text.clone(),
+                    message: format!("part {}: expected {:?}, got {:?}", part + 1, expected.to_string(), solved.answer.to_string()),
+                }),
+                _ => {},
+            }
+        }
+    }
+    Some(problems)
+}
+
+#[test]
+fn generated_inputs_have_the_answers_they_were_made_with() {
+    let days = crate::days::DAYS;
+    let problems = crate::pool::map(days, days.len(), |day| stress(day, 0, 3).unwrap_or_default());
+    for (day, problems) in days.iter().zip(problems) {
+        if let Some(problem) = problems.first() {
+            panic!("{} day {:02}, seed {}: {}", day.year, day.day, problem.seed, problem.message);
+        }
+    }
+}
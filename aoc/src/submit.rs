@@ -0,0 +1,246 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use regex::Regex;
+
+use crate::inputs::Inputs;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+/// What the server made of a submission.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Response {
+    Verdict(Verdict, Option<Duration>),
+    Cooldown(Duration),
+    AlreadySolved,
+    Unknown,
+}
+
+/// One line of the history: `<unix time> <part> <verdict> <answer>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub time: u64,
+    pub part: usize,
+    pub verdict: Verdict,
+    pub answer: String,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Verdict::Right => "right",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+        })
+    }
+}
+
+impl Verdict {
+    fn parse(s: &str) -> Option<Verdict> {
+        match s {
+            "right" => Some(Verdict::Right),
+            "wrong" => Some(Verdict::Wrong),
+            "too-high" => Some(Verdict::TooHigh),
+            "too-low" => Some(Verdict::TooLow),
+            _ => None,
+        }
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+/// Reads a wait like `4m 32s` or `one minute` from the server's message.
+fn wait(text: &str) -> Option<Duration> {
+    let left = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+    if let Some(c) = left.captures(text) {
+        let minutes: u64 = c.get(1).map_or(0, |m| m.as_str().parse().unwrap_or(0));
+        let seconds: u64 = c[2].parse().unwrap_or(0);
+        return Some(Duration::from_secs(minutes * 60 + seconds));
+    }
+    let minutes = Regex::new(r"wait (one|\d+) minutes? before trying again").unwrap();
+    let c = minutes.captures(text)?;
+    let n: u64 = if &c[1] == "one" { 1 } else { c[1].parse().ok()? };
+    Some(Duration::from_secs(n * 60))
+}
+
+/// Interprets the page returned for a submission.
+pub fn interpret(page: &str) -> Response {
+    if page.contains("That's the right answer") {
+        Response::Verdict(Verdict::Right, None)
+    } else if page.contains("That's not the right answer") {
+        let verdict = if page.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if page.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        };
+        Response::Verdict(verdict, wait(page))
+    } else if page.contains("You gave an answer too recently") {
+        Response::Cooldown(wait(page).unwrap_or(Duration::from_secs(60)))
+    } else if page.contains("You don't seem to be solving the right level") {
+        Response::AlreadySolved
+    } else {
+        Response::Unknown
+    }
+}
+
+pub fn parse_history(text: &str) -> Vec<Entry> {
+    text.lines().filter_map(|line| {
+        let mut fields = line.splitn(4, ' ');
+        Some(Entry {
+            time: fields.next()?.parse().ok()?,
+            part: fields.next()?.parse().ok()?,
+            verdict: Verdict::parse(fields.next()?)?,
+            answer: fields.next()?.to_string(),
+        })
+    }).collect()
+}
+
+/// Why `answer` must not be submitted, judging by earlier submissions.
+pub fn objection(history: &[Entry], part: usize, answer: &str) -> Option<String> {
+    let value: Option<i128> = answer.parse().ok();
+    for entry in history.iter().filter(|e| e.part == part) {
+        let known: Option<i128> = entry.answer.parse().ok();
+        match (entry.verdict, value, known) {
+            (Verdict::Right, _, _) =>
+                return Some(format!("part {} was already solved with {}", part, entry.answer)),
+            (_, _, _) if entry.answer == answer =>
+                return Some(format!("{} was already submitted and is {}", answer, entry.verdict)),
+            (Verdict::TooHigh, Some(v), Some(k)) if v >= k =>
+                return Some(format!("{} is too high; {} already was", answer, entry.answer)),
+            (Verdict::TooLow, Some(v), Some(k)) if v <= k =>
+                return Some(format!("{} is too low; {} already was", answer, entry.answer)),
+            _ => {},
+        }
+    }
+    None
+}
+
+/// Keeps the submissions of every day next to its input, and the time until
+/// which the server refuses answers in `<dir>/.cooldown`.
+pub struct Submitter<'a> {
+    pub inputs: &'a Inputs,
+}
+
+impl<'a> Submitter<'a> {
+    pub fn history_file(&self, year: u16, day: u8) -> PathBuf {
+        self.inputs.path(year, day).with_file_name("submissions.txt")
+    }
+
+    fn cooldown_file(&self) -> PathBuf {
+        self.inputs.dir.join(".cooldown")
+    }
+
+    pub fn history(&self, year: u16, day: u8) -> Vec<Entry> {
+        parse_history(&fs::read_to_string(self.history_file(year, day)).unwrap_or_default())
+    }
+
+    fn record(&self, year: u16, day: u8, entry: &Entry) -> Result<(), String> {
+        let path = self.history_file(year, day);
+        let mut text = fs::read_to_string(&path).unwrap_or_default();
+        text += &format!("{} {} {} {}\n", entry.time, entry.part, entry.verdict, entry.answer);
+        write(&path, &text)
+    }
+
+    /// Seconds left until the server accepts answers again.
+    pub fn cooldown(&self) -> Option<u64> {
+        let until: u64 = fs::read_to_string(self.cooldown_file()).ok()?.trim().parse().ok()?;
+        until.checked_sub(now()).filter(|&left| left > 0)
+    }
+
+    fn cool_down(&self, wait: Duration) -> Result<(), String> {
+        write(&self.cooldown_file(), &(now() + wait.as_secs()).to_string())
+    }
+
+    /// Submits an answer unless it is known to be wrong or the server is
+    /// still cooling down, and records the verdict. Errors describe why
+    /// nothing was submitted or what went wrong.
+    pub fn submit(&self, year: u16, day: u8, part: usize, answer: &str) -> Result<Verdict, String> {
+        if answer.is_empty() || answer.contains('\n') {
+            return Err(format!("{:?} cannot be submitted", answer));
+        }
+        if let Some(reason) = objection(&self.history(year, day), part, answer) {
+            return Err(reason);
+        }
+        if let Some(left) = self.cooldown() {
+            return Err(format!("cooling down for another {}s", left));
+        }
+        let fetcher = self.inputs.fetcher.as_ref().ok_or("AOC_SESSION is not set")?;
+        match interpret(&fetcher.post_answer(year, day, part, answer)?) {
+            Response::Verdict(verdict, wait) => {
+                if let Some(wait) = wait {
+                    self.cool_down(wait)?;
+                }
+                self.record(year, day, &Entry { time: now(), part, verdict, answer: answer.to_string() })?;
+                Ok(verdict)
+            },
+            Response::Cooldown(wait) => {
+                self.cool_down(wait)?;
+                Err(format!("the server asks to wait another {}s", wait.as_secs()))
+            },
+            Response::AlreadySolved => Err(format!("part {} is already solved or not unlocked", part)),
+            Response::Unknown => Err(String::from("unrecognised response from the server")),
+        }
+    }
+}
+
+fn write(path: &Path, text: &str) -> Result<(), String> {
+    let failed = |_| format!("failed to write {}", path.display());
+    fs::create_dir_all(path.parent().unwrap()).map_err(failed)?;
+    fs::write(path, text).map_err(failed)
+}
+
+#[cfg(test)]
+const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high. \
+    Please wait one minute before trying again.</p></article>";
+
+#[test]
+fn pages_are_interpreted() {
+    assert_eq!(interpret("<p>That's the right answer! You are one gold star closer.</p>"),
+        Response::Verdict(Verdict::Right, None));
+    assert_eq!(interpret(TOO_HIGH), Response::Verdict(Verdict::TooHigh, Some(Duration::from_secs(60))));
+    assert_eq!(interpret("You gave an answer too recently. You have 4m 32s left to wait."),
+        Response::Cooldown(Duration::from_secs(272)));
+}
+
+#[test]
+fn known_wrong_answers_are_refused() {
+    let history = parse_history("1 1 too-high 500\n2 1 wrong 300\n3 2 right 7\n");
+    assert!(objection(&history, 1, "300").is_some());
+    assert!(objection(&history, 1, "501").is_some());
+    assert!(objection(&history, 1, "499").is_none());
+    assert!(objection(&history, 2, "8").is_some());
+}
+
+#[test]
+fn submissions_are_recorded_and_cool_down() {
+    let server = crate::mock::Server::start(|_| (200, String::from(TOO_HIGH)));
+    let dir = crate::inputs::temporary("submit");
+    let inputs = crate::inputs::inputs(&dir, &server.url, Duration::ZERO);
+    let submitter = Submitter { inputs: &inputs };
+    let first = submitter.submit(2021, 1, 1, "1234");
+    let again = submitter.submit(2021, 1, 1, "1200");
+    let history = submitter.history(2021, 1);
+    let requests = server.requests();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(first, Ok(Verdict::TooHigh));
+    assert!(again.unwrap_err().contains("cooling down"));
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].answer, "1234");
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].line, "POST /2021/day/1/answer HTTP/1.1");
+    assert_eq!(requests[0].body, "level=1&answer=1234");
+}
@@ -0,0 +1,118 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, IsTerminal, Write};
+
+use tracing_flame::{FlameLayer, FlushGuard};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::prelude::*;
+
+/// Where spans go. `-v` prints parsing and each part with their times on
+/// stderr, `-vv` adds the inner loops that days trace and `-vvv` everything;
+/// `--flame <path>` writes every span as folded stacks for `inferno-flamegraph`
+/// or `flamegraph.pl`. Days solved in processes of their own are traced the
+/// same way and add their stacks to the same file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Trace {
+    pub verbosity: usize,
+    pub flame: Option<String>,
+}
+
+/// Takes the tracing options out of `args`, wherever they are; fails if
+/// `--flame` has no path.
+pub fn options(args: impl IntoIterator<Item = String>) -> Result<(Trace, Vec<String>), &'static str> {
+    let mut trace = Trace::default();
+    let mut rest = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--flame" => trace.flame = Some(args.next().ok_or("flame graph path")?),
+            _ if arg.len() > 1 && arg.strip_prefix('-').is_some_and(|v| v.chars().all(|c| c == 'v')) => {
+                trace.verbosity += arg.len() - 1;
+            },
+            _ => rest.push(arg),
+        }
+    }
+    Ok((trace, rest))
+}
+
+/// Folded stacks kept until they are flushed, then added to the end of the
+/// file in one write, so that processes tracing at once do not cut into
+/// each other's lines.
+pub struct Appender {
+    path: String,
+    stacks: Vec<u8>,
+}
+
+impl Write for Appender {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.stacks.extend_from_slice(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.stacks.is_empty() {
+            OpenOptions::new().append(true).open(&self.path)?.write_all(&self.stacks)?;
+            self.stacks.clear();
+        }
+        Ok(())
+    }
+}
+
+impl Trace {
+    /// The options that trace a child process the same way.
+    pub fn args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if self.verbosity > 0 {
+            args.push(format!("-{}", "v".repeat(self.verbosity)));
+        }
+        if let Some(path) = &self.flame {
+            args.extend([String::from("--flame"), path.clone()]);
+        }
+        args
+    }
+
+    /// Installs the subscribers asked for, if any; the flame graph is only
+    /// complete once the guard is dropped. A flame graph is started afresh
+    /// unless `append`ing to a parent's. Fails with the path if the flame
+    /// graph cannot be written.
+    pub fn start(&self, append: bool) -> Result<Option<FlushGuard<Appender>>, String> {
+        if self.verbosity == 0 && self.flame.is_none() {
+            return Ok(None);
+        }
+        let level = match self.verbosity {
+            0 => LevelFilter::OFF,
+            1 => LevelFilter::INFO,
+            2 => LevelFilter::DEBUG,
+            _ => LevelFilter::TRACE,
+        };
+        let text = tracing_subscriber::fmt::layer()
+            .with_writer(io::stderr)
+            .with_span_events(FmtSpan::CLOSE)
+            .with_target(false)
+            .with_ansi(io::stderr().is_terminal())
+            .with_filter(level);
+        let (flame, guard) = match &self.flame {
+            Some(path) => {
+                if !append {
+                    File::create(path).map_err(|_| path.clone())?;
+                }
+                let layer = FlameLayer::new(Appender { path: path.clone(), stacks: Vec::new() });
+                let guard = layer.flush_on_drop();
+                (Some(layer), Some(guard))
+            },
+            None => (None, None),
+        };
+        tracing_subscriber::registry().with(text).with(flame).init();
+        Ok(guard)
+    }
+}
+
+#[test]
+fn tracing_options_are_taken_from_anywhere() {
+    let args = ["run", "-vv", "2021", "19", "--flame", "out.folded", "-v", "-"].map(String::from);
+    let (trace, rest) = options(args).unwrap();
+    assert_eq!(trace, Trace { verbosity: 3, flame: Some(String::from("out.folded")) });
+    assert_eq!(rest, ["run", "2021", "19", "-"]);
+    assert_eq!(trace.args(), ["-vvv", "--flame", "out.folded"]);
+    assert!(options([String::from("--flame")]).is_err());
+}
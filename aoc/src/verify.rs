@@ -0,0 +1,99 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use aoc_common::{Answer, Failure};
+use aoc_common::fixture::{escape, parse_answers, Expected};
+
+use crate::days::Day;
+use crate::limits::{self, Limits};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    Missing,
+    Error,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Status::Pass => "pass",
+            Status::Fail => "FAIL",
+            Status::Missing => "missing",
+            Status::Error => "ERROR",
+        })
+    }
+}
+
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: usize,
+    pub status: Status,
+    pub expected: String,
+    pub actual: String,
+}
+
+/// Stored answers live next to the input, in `<year>/dayNN/answers.txt`.
+pub fn answers_file(input: &Path) -> PathBuf {
+    input.with_file_name("answers.txt")
+}
+
+fn record(day: &Day, part: usize, status: Status, expected: &str, actual: &str) -> Record {
+    Record {
+        year: day.year,
+        day: day.day,
+        part,
+        status,
+        expected: escape(expected),
+        actual: escape(actual),
+    }
+}
+
+/// Runs a day on its input within the limits and compares both parts
+/// against the stored answers.
+pub fn verify(day: &Day, input: &Path, limits: Limits) -> Vec<Record> {
+    let expected = match aoc_common::fread(&answers_file(input).to_string_lossy()) {
+        Ok(text) => parse_answers(&text),
+        Err(_) => Ok(Expected::new()),
+    };
+    let expected = match expected {
+        Ok(expected) => expected,
+        Err(e) => {
+            let e = format!("{}: {}", answers_file(input).display(), e);
+            return (1..=2).map(|part| record(day, part, Status::Error, "", &e)).collect();
+        }
+    };
+    let expect = |part: usize| expected.get(&part).map(String::as_str).unwrap_or("");
+    let answers: Result<[Answer; 2], Failure> = match aoc_common::fread(&input.to_string_lossy()) {
+        Ok(text) => limits::solve(day, &text, limits).map(|solved| solved.map(|s| s.answer)),
+        Err(_) => {
+            return (1..=2).map(|part| record(day, part, Status::Missing, expect(part), "no input")).collect();
+        }
+    };
+    (1..=2).map(|part| {
+        match (&answers, expected.get(&part)) {
+            (Err(e), _) => record(day, part, Status::Error, expect(part), &e.to_string()),
+            (Ok(answers), None) => record(day, part, Status::Missing, "", &answers[part - 1].to_string()),
+            (Ok(answers), Some(value)) => {
+                let actual = answers[part - 1].to_string();
+                let status = if &actual == value { Status::Pass } else { Status::Fail };
+                record(day, part, status, value, &actual)
+            },
+        }
+    }).collect()
+}
+
+pub fn print_table(records: &[Record]) {
+    let expected = records.iter()
+        .map(|r| r.expected.chars().count())
+        .chain(["expected".len()])
+        .max()
+        .unwrap_or(0);
+    println!("year  day  part  status   {:expected$}  actual", "expected");
+    for r in records {
+        println!("{:4}  {:3}  {:4}  {:7}  {:expected$}  {}",
+            r.year, r.day, r.part, r.status, r.expected, r.actual);
+    }
+}
@@ -0,0 +1,247 @@
+use std::fs;
+use std::io::{self, IsTerminal, Read, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::grid::Grid;
+
+/// Frames drawn before an animation that never finishes is cut off.
+pub const FRAME_LIMIT: usize = 10_000;
+
+/// One character of a frame, optionally in a colour of the 256-colour ANSI
+/// palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub glyph: char,
+    pub colour: Option<u8>,
+}
+
+impl Cell {
+    pub fn plain(glyph: char) -> Cell {
+        Cell { glyph, colour: None }
+    }
+
+    pub fn coloured(glyph: char, colour: u8) -> Cell {
+        Cell { glyph, colour: Some(colour) }
+    }
+}
+
+impl Default for Cell {
+    fn default() -> Cell {
+        Cell::plain(' ')
+    }
+}
+
+pub const RED: u8 = 9;
+pub const GREEN: u8 = 10;
+pub const YELLOW: u8 = 11;
+pub const WHITE: u8 = 15;
+
+/// The palette's grey for `level` out of `max`, from near black to near white.
+pub fn grey(level: usize, max: usize) -> u8 {
+    232 + (23 * level.min(max) / max.max(1)) as u8
+}
+
+/// A simulation that can be watched one step at a time.
+pub trait RenderFrame {
+    /// The current state.
+    fn frame(&self) -> Grid<Cell>;
+
+    /// Advances the simulation by a step; `false` if it had already
+    /// finished.
+    fn step(&mut self) -> bool;
+}
+
+/// Plain text of a frame, one line per row.
+pub fn text(frame: &Grid<Cell>) -> String {
+    frame.render(|cell| cell.glyph).iter().map(|row| format!("{}\n", row)).collect()
+}
+
+/// A frame with ANSI colours, drawn over the previous one.
+pub fn ansi(frame: &Grid<Cell>) -> String {
+    let mut result = String::from("\x1b[H\x1b[2J");
+    let mut colour = None;
+    for (x, y) in frame.points() {
+        let cell = frame[(x, y)];
+        if x == 0 && y > 0 {
+            result += "\x1b[0m\n";
+            colour = None;
+        }
+        if cell.colour != colour {
+            match cell.colour {
+                Some(c) => result += &format!("\x1b[38;5;{}m", c),
+                None => result += "\x1b[0m",
+            }
+            colour = cell.colour;
+        }
+        result.push(cell.glyph);
+    }
+    result + "\x1b[0m\n"
+}
+
+/// Where and how fast frames are shown. In a terminal, space pauses and
+/// resumes, `n` steps while paused and `q` or Ctrl-C quits; headless, every
+/// frame is written to `<dir>/frame-NNNNN.txt` without delay.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Animation {
+    pub fps: f64,
+    pub dir: Option<PathBuf>,
+}
+
+enum Key {
+    Pause,
+    Step,
+    Quit,
+}
+
+/// Puts the terminal into character mode while alive, reading keys on a
+/// background thread. Signal keys are read as keys too, so that Ctrl-C
+/// quits through `Drop` and gets the echo back; a process killed from
+/// outside leaves the terminal as it was, which `stty sane` repairs.
+struct Keys {
+    saved: Option<String>,
+    keys: Receiver<Key>,
+}
+
+impl Keys {
+    fn listen() -> Keys {
+        let (sender, keys) = mpsc::channel();
+        if !io::stdin().is_terminal() {
+            return Keys { saved: None, keys };
+        }
+        let saved = stty(&["-g"]).map(|s| s.trim().to_string());
+        stty(&["-icanon", "-echo", "-isig", "min", "1"]);
+        thread::spawn(move || {
+            for byte in io::stdin().lock().bytes() {
+                let key = match byte {
+                    Ok(b' ') => Key::Pause,
+                    Ok(b'n') => Key::Step,
+                    // Ctrl-C and Ctrl-\, which no longer raise signals.
+                    Ok(b'q' | 0x03 | 0x1c) | Err(_) => Key::Quit,
+                    Ok(_) => continue,
+                };
+                if sender.send(key).is_err() {
+                    break;
+                }
+            }
+        });
+        Keys { saved, keys }
+    }
+
+    /// The next key pressed, waiting for at most `timeout` unless paused.
+    fn next(&self, paused: bool, timeout: Duration) -> Option<Key> {
+        if paused {
+            return self.keys.recv().ok();
+        }
+        match self.keys.recv_timeout(timeout) {
+            Ok(key) => Some(key),
+            Err(RecvTimeoutError::Timeout) => None,
+            Err(RecvTimeoutError::Disconnected) => {
+                thread::sleep(timeout);
+                None
+            },
+        }
+    }
+}
+
+impl Drop for Keys {
+    fn drop(&mut self) {
+        if let Some(saved) = &self.saved {
+            stty(&[saved]);
+        }
+    }
+}
+
+fn stty(args: &[&str]) -> Option<String> {
+    let output = Command::new("stty").args(args).stdin(Stdio::inherit()).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+impl Animation {
+    /// Runs the simulation to its end, showing every frame, and returns
+    /// how many frames were shown.
+    pub fn play(&self, simulation: &mut dyn RenderFrame) -> io::Result<usize> {
+        match &self.dir {
+            Some(_) => self.dump(simulation),
+            None => self.show(simulation),
+        }
+    }
+
+    fn dump(&self, simulation: &mut dyn RenderFrame) -> io::Result<usize> {
+        let dir = self.dir.as_ref().unwrap();
+        fs::create_dir_all(dir)?;
+        let mut frames = 0;
+        loop {
+            fs::write(dir.join(format!("frame-{:05}.txt", frames)), text(&simulation.frame()))?;
+            frames += 1;
+            if frames >= FRAME_LIMIT || !simulation.step() {
+                return Ok(frames);
+            }
+        }
+    }
+
+    fn show(&self, simulation: &mut dyn RenderFrame) -> io::Result<usize> {
+        let keys = Keys::listen();
+        let interval = Duration::from_secs_f64(1.0 / self.fps);
+        let mut stdout = io::stdout();
+        let mut paused = false;
+        let mut frames = 0;
+        loop {
+            let shown = Instant::now();
+            write!(stdout, "{}", ansi(&simulation.frame()))?;
+            stdout.flush()?;
+            frames += 1;
+            let mut step = false;
+            while !step {
+                match keys.next(paused, interval.saturating_sub(shown.elapsed())) {
+                    Some(Key::Pause) => paused = !paused,
+                    Some(Key::Step) => step = paused,
+                    Some(Key::Quit) => return Ok(frames),
+                    None if paused => return Ok(frames),
+                    None => step = shown.elapsed() >= interval,
+                }
+            }
+            if frames >= FRAME_LIMIT || !simulation.step() {
+                return Ok(frames);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+struct Countdown(usize);
+
+#[cfg(test)]
+impl RenderFrame for Countdown {
+    fn frame(&self) -> Grid<Cell> {
+        Grid::new(1, 1, vec![Cell::coloured(char::from_digit(self.0 as u32, 10).unwrap(), RED)])
+    }
+
+    fn step(&mut self) -> bool {
+        if self.0 == 1 {
+            return false;
+        }
+        self.0 -= 1;
+        true
+    }
+}
+
+#[test]
+fn headless_animations_dump_every_frame() {
+    let dir = std::env::temp_dir().join(format!("aoc-frames-{}", std::process::id()));
+    let animation = Animation { fps: 10.0, dir: Some(dir.clone()) };
+    let frames = animation.play(&mut Countdown(3));
+    let last = fs::read_to_string(dir.join("frame-00002.txt"));
+    fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(frames.unwrap(), 3);
+    assert_eq!(last.unwrap(), "1\n");
+}
+
+#[test]
+fn frames_are_coloured_with_ansi_codes() {
+    let frame = Grid::new(2, 1, vec![Cell::coloured('#', GREEN), Cell::plain('.')]);
+    assert_eq!(ansi(&frame), "\x1b[H\x1b[2J\x1b[38;5;10m#\x1b[0m.\x1b[0m\n");
+}
@@ -0,0 +1,143 @@
+use std::fmt;
+
+/// A stretch of the input text; line and column count from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+    pub length: usize,
+}
+
+impl Span {
+    /// The span of `token`, which must be a slice of `text`.
+    pub fn of(text: &str, token: &str) -> Span {
+        let start = (token.as_ptr() as usize).wrapping_sub(text.as_ptr() as usize);
+        let start = if start <= text.len() && text.is_char_boundary(start) { start } else { 0 };
+        let before = &text[..start];
+        Span {
+            line: before.matches('\n').count() + 1,
+            column: before.rsplit('\n').next().unwrap_or("").chars().count() + 1,
+            length: token.chars().count(),
+        }
+    }
+
+    /// The span of the character at byte `offset` of `line`, which must be
+    /// a slice of `text`.
+    pub fn at(text: &str, line: &str, offset: usize) -> Span {
+        let length = line[offset..].chars().next().map_or(0, char::len_utf8);
+        Span::of(text, &line[offset..offset + length])
+    }
+}
+
+/// What kind of error occurred; each class has its own exit status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Class {
+    Usage,
+    Input,
+    Parse,
+    Solve,
+    /// A day ran out of time or memory allowed by the runner.
+    Limit,
+}
+
+impl Class {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Class::Usage => 2,
+            Class::Input => 3,
+            Class::Parse => 4,
+            Class::Solve => 5,
+            Class::Limit => 6,
+        }
+    }
+}
+
+/// An error that can point at the part of the input that caused it.
+pub trait Diagnostic: fmt::Display + fmt::Debug {
+    fn class(&self) -> Class;
+
+    fn span(&self) -> Option<Span> {
+        None
+    }
+}
+
+impl Diagnostic for std::convert::Infallible {
+    fn class(&self) -> Class {
+        match *self {}
+    }
+}
+
+/// A diagnostic detached from the error type it was created from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    pub class: Class,
+    pub message: String,
+    pub span: Option<Span>,
+}
+
+impl Failure {
+    pub fn new<E: Diagnostic + ?Sized>(error: &E) -> Failure {
+        Failure {
+            class: error.class(),
+            message: error.to_string(),
+            span: error.span(),
+        }
+    }
+
+    /// Prints the diagnostic on stderr and returns its class.
+    pub fn report(self, file: &str, text: &str) -> Class {
+        eprint!("{}", self.render(file, text));
+        self.class
+    }
+
+    /// The error message followed by the location and a caret snippet of
+    /// the offending input, in the style of rustc.
+    pub fn render(&self, file: &str, text: &str) -> String {
+        let mut result = format!("error: {}\n", self.message);
+        let span = match self.span {
+            Some(span) => span,
+            None => {
+                if !file.is_empty() {
+                    result += &format!("  --> {}\n", file);
+                }
+                return result;
+            }
+        };
+        let number = span.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let source = text.lines().nth(span.line - 1).unwrap_or("");
+        result += &format!("{}--> {}:{}:{}\n", gutter, file, span.line, span.column);
+        result += &format!("{} |\n", gutter);
+        result += &format!("{} | {}\n", number, source);
+        result += &format!("{} | {}{}\n", gutter,
+            " ".repeat(span.column - 1), "^".repeat(span.length.max(1)));
+        result
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.span {
+            Some(span) => write!(f, "{} (line {}, column {})", self.message, span.line, span.column),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+#[test]
+fn spans_point_into_the_text() {
+    let text = "1x2x3\n4x5y6\n";
+    let token = &text[10..11];
+    assert_eq!(Span::of(text, token), Span { line: 2, column: 5, length: 1 });
+}
+
+#[test]
+fn failures_render_a_caret_snippet() {
+    let failure = Failure {
+        class: Class::Parse,
+        message: String::from("Invalid character: y"),
+        span: Some(Span { line: 2, column: 4, length: 1 }),
+    };
+    assert_eq!(failure.render("input.txt", "1x2x3\n4x5y6\n"),
+        "error: Invalid character: y\n --> input.txt:2:4\n  |\n2 | 4x5y6\n  |    ^\n");
+}
@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{Answer, Solution};
+
+/// Expected answers of one input, keyed by part.
+pub type Expected = HashMap<usize, String>;
+
+/// Answers are stored one part per line as `<part>: <value>`; line breaks
+/// of multi-line answers are written as `\n`. A value of `! <message>`
+/// expects the part to fail with that message instead.
+pub const FAILURE: &str = "! ";
+
+pub fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+pub fn unescape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => { result.push('\n'); chars.next(); },
+            ('\\', Some('\\')) => { result.push('\\'); chars.next(); },
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+pub fn parse_answers(text: &str) -> Result<Expected, String> {
+    let mut expected = Expected::new();
+    for (k, line) in text.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (part, value) = line.split_once(':')
+            .ok_or_else(|| format!("line {}: expected `<part>: <value>`", k + 1))?;
+        let part: usize = part.trim().parse()
+            .map_err(|_| format!("line {}: invalid part {:?}", k + 1, part.trim()))?;
+        let value = value.trim_end_matches('\r');
+        expected.insert(part, unescape(value.strip_prefix(' ').unwrap_or(value)));
+    }
+    Ok(expected)
+}
+
+/// The example inputs in `dir`, each a `<name>.txt` with its expected
+/// answers in `<name>.answers`, ordered by name.
+pub fn fixtures(dir: &Path) -> Vec<(String, PathBuf)> {
+    let mut result: Vec<(String, PathBuf)> = fs::read_dir(dir).into_iter().flatten()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|e| e == "txt") && path.with_extension("answers").is_file())
+        .filter_map(|path| Some((path.file_stem()?.to_string_lossy().into_owned(), path)))
+        .collect();
+    result.sort();
+    result
+}
+
+/// Solves the parts listed in `expected`, and only those, and describes
+/// every answer that differs. An input that does not parse fails every
+/// part with the parse error.
+pub fn check<S: Solution>(text: &str, expected: &str) -> Result<(), String> {
+    let expected = parse_answers(expected)?;
+    let input = S::parse(text);
+    let mut parts: Vec<_> = expected.into_iter().collect();
+    parts.sort();
+    let mut failures = Vec::new();
+    for (part, value) in parts {
+        let answer: Result<Answer, String> = match (part, &input) {
+            (1 | 2, Err(e)) => Err(e.to_string()),
+            (1, Ok(input)) => S::part1(input).map(Into::into).map_err(|e| e.to_string()),
+            (2, Ok(input)) => S::part2(input).map(Into::into).map_err(|e| e.to_string()),
+            _ => return Err(format!("there is no part {}", part)),
+        };
+        match (answer, value.strip_prefix(FAILURE)) {
+            (Ok(answer), None) if answer.to_string() == value => {},
+            (Err(e), Some(message)) if e == message => {},
+            (Ok(answer), _) => failures.push(format!("part {}: expected {:?}, got {:?}", part, value, answer.to_string())),
+            (Err(e), _) => failures.push(format!("part {}: {}", part, e)),
+        }
+    }
+    if failures.is_empty() { Ok(()) } else { Err(failures.join("\n")) }
+}
+
+#[test]
+fn answers_file_round_trips_multi_line_values() {
+    let art = "#  #\n\\ #";
+    let text = format!("# 2021 day 13\n1: 17\n2: {}\n", escape(art));
+    let expected = parse_answers(&text).unwrap();
+    assert_eq!(expected[&1], "17");
+    assert_eq!(expected[&2], art);
+}
+
+#[test]
+fn answers_file_rejects_malformed_lines() {
+    assert!(parse_answers("1 17").is_err());
+    assert!(parse_answers("one: 17").is_err());
+}
+
+#[test]
+fn only_the_expected_parts_are_checked() {
+    struct Sum;
+    impl Solution for Sum {
+        type Input = Vec<i32>;
+        type Error = crate::Error;
+        type Part1 = i32;
+        type Part2 = i32;
+        fn parse(input: &str) -> Result<Vec<i32>, crate::Error> {
+            Ok(crate::trimmed(input).iter().filter_map(|n| n.parse().ok()).collect())
+        }
+        fn part1(input: &Vec<i32>) -> Result<i32, crate::Error> {
+            Ok(input.iter().sum())
+        }
+        fn part2(_: &Vec<i32>) -> Result<i32, crate::Error> {
+            Err(crate::Error::MissingArgument)
+        }
+    }
+    assert_eq!(check::<Sum>("1\n2\n", "1: 3\n"), Ok(()));
+    assert!(check::<Sum>("1\n2\n", "1: 4\n").unwrap_err().contains("expected \"4\""));
+    assert!(check::<Sum>("1\n2\n", "2: 3\n").is_err());
+    assert_eq!(check::<Sum>("1\n2\n", "2: ! Please specify input file.\n"), Ok(()));
+}
@@ -0,0 +1,101 @@
+use std::ops::RangeInclusive;
+
+use crate::Answer;
+
+/// A random puzzle input, with the answer to each part when it is known by
+/// construction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated {
+    pub text: String,
+    pub answers: [Option<Answer>; 2],
+}
+
+impl Generated {
+    /// An input whose answers are unknown; it only has to be solvable.
+    pub fn text(text: String) -> Generated {
+        Generated { text, answers: [None, None] }
+    }
+
+    pub fn answered(text: String, part1: impl Into<Answer>, part2: impl Into<Answer>) -> Generated {
+        Generated { text, answers: [Some(part1.into()), Some(part2.into())] }
+    }
+}
+
+/// A small seeded random number generator (SplitMix64), so that the same
+/// seed always generates the same input.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number below `n`, which must not be 0.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "no number is below 0");
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (low, high) = (*range.start(), *range.end());
+        low + self.below((high - low) as u64 + 1) as i64
+    }
+
+    /// A number in `range` as a `usize`.
+    pub fn size(&mut self, range: RangeInclusive<usize>) -> usize {
+        self.range(*range.start() as i64..=*range.end() as i64) as usize
+    }
+
+    /// `true` with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64) < p * (1u64 << 53) as f64
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for k in (1..items.len()).rev() {
+            items.swap(k, self.below(k as u64 + 1) as usize);
+        }
+    }
+}
+
+/// Copies of `text` with one line replaced by one of its `variants`, in
+/// every way, for [`Solution::simplify`](crate::Solution::simplify).
+pub fn vary_lines(text: &str, variants: impl Fn(&str) -> Vec<String>) -> Vec<String> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut result = Vec::new();
+    for (k, line) in lines.iter().enumerate() {
+        for variant in variants(line) {
+            let mut varied = String::new();
+            for (j, &line) in lines.iter().enumerate() {
+                varied += if j == k { &variant } else { line };
+                varied.push('\n');
+            }
+            result.push(varied);
+        }
+    }
+    result
+}
+
+#[test]
+fn seeds_reproduce_their_numbers() {
+    let numbers = |seed| {
+        let mut rng = Rng::new(seed);
+        (0..8).map(|_| rng.range(-3..=3)).collect::<Vec<_>>()
+    };
+    assert_eq!(numbers(7), numbers(7));
+    assert_ne!(numbers(7), numbers(8));
+    assert!(numbers(7).iter().all(|n| (-3..=3).contains(n)));
+}
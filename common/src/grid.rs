@@ -0,0 +1,245 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::{trimmed, Class, Diagnostic, Span};
+
+/// A position in a grid as `(x, y)`, with `y` counting rows from the top.
+pub type Point = (usize, usize);
+
+/// The four orthogonal neighbours.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// The eight neighbours including diagonals, in reading order.
+pub const SURROUNDING: [(isize, isize); 8] = [
+    (-1, -1), (0, -1), (1, -1),
+    (-1, 0),           (1, 0),
+    (-1, 1),  (0, 1),  (1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    InvalidCharacter(char, Span),
+    RaggedRow(Span),
+    Empty,
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GridError::InvalidCharacter(c, _) => write!(f, "Invalid character in map: {}", c),
+            GridError::RaggedRow(_) => write!(f, "All rows of the map must have the same length."),
+            GridError::Empty => write!(f, "The map is empty."),
+        }
+    }
+}
+
+impl Diagnostic for GridError {
+    fn class(&self) -> Class {
+        Class::Parse
+    }
+
+    fn span(&self) -> Option<Span> {
+        match self {
+            GridError::InvalidCharacter(_, span) | GridError::RaggedRow(span) => Some(*span),
+            GridError::Empty => None,
+        }
+    }
+}
+
+/// A rectangle of cells stored row by row. A toroidal grid wraps around at
+/// its edges when stepping to neighbours.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+    toroidal: bool,
+}
+
+impl<T> Grid<T> {
+    /// A grid of the given size from its cells in row-major order.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(cells.len(), width * height, "grid of {}×{} cells", width, height);
+        Grid { width, height, cells, toroidal: false }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T> where T: Clone {
+        Grid::new(width, height, vec![value; width * height])
+    }
+
+    /// A grid of the given size with each cell computed from its position.
+    pub fn from_fn(width: usize, height: usize, mut cell: impl FnMut(Point) -> T) -> Grid<T> {
+        let cells = (0..height).flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut cell)
+            .collect();
+        Grid::new(width, height, cells)
+    }
+
+    /// Reads a character map, one row per non-blank line; `cell` maps each
+    /// character to a cell or rejects it with `None`.
+    pub fn parse(text: &str, cell: impl FnMut(char) -> Option<T>) -> Result<Grid<T>, GridError> {
+        Grid::from_rows(text, &trimmed(text), cell)
+    }
+
+    /// Like [`Grid::parse`] for some of the lines of `text`; errors point
+    /// into `text`.
+    pub fn from_rows(text: &str, rows: &[&str], mut cell: impl FnMut(char) -> Option<T>) -> Result<Grid<T>, GridError> {
+        let width = rows.first().map_or(0, |row| row.chars().count());
+        if width == 0 {
+            return Err(GridError::Empty);
+        }
+        let mut cells = Vec::with_capacity(width * rows.len());
+        for row in rows {
+            let start = cells.len();
+            for (k, c) in row.char_indices() {
+                cells.push(cell(c).ok_or_else(|| GridError::InvalidCharacter(c, Span::at(text, row, k)))?);
+            }
+            if cells.len() - start != width {
+                return Err(GridError::RaggedRow(Span::of(text, row)));
+            }
+        }
+        Ok(Grid::new(width, rows.len(), cells))
+    }
+
+    pub fn toroidal(mut self) -> Grid<T> {
+        self.toroidal = true;
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, (x, y): Point) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.contains(p).then(|| &self.cells[p.1 * self.width + p.0])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        let width = self.width;
+        if self.contains(p) { Some(&mut self.cells[p.1 * width + p.0]) } else { None }
+    }
+
+    /// The cell at any position, wrapping around the edges; `None` if the
+    /// grid has no cells to wrap around to.
+    pub fn wrapped(&self, x: isize, y: isize) -> Option<&T> {
+        if self.is_empty() {
+            return None;
+        }
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        self.get((x, y))
+    }
+
+    /// The position one step of `(dx, dy)` away, if it is on the grid or
+    /// the grid is toroidal.
+    pub fn step(&self, (x, y): Point, (dx, dy): (isize, isize)) -> Option<Point> {
+        let (x, y) = (x as isize + dx, y as isize + dy);
+        let (w, h) = (self.width as isize, self.height as isize);
+        if self.toroidal && w > 0 && h > 0 {
+            Some((x.rem_euclid(w) as usize, y.rem_euclid(h) as usize))
+        } else if (0..w).contains(&x) && (0..h).contains(&y) {
+            Some((x as usize, y as usize))
+        } else {
+            None
+        }
+    }
+
+    /// The positions around `p` reached by `offsets`, such as [`ORTHOGONAL`]
+    /// or [`SURROUNDING`].
+    pub fn neighbours<'a>(&'a self, p: Point, offsets: &'a [(isize, isize)]) -> impl Iterator<Item = Point> + 'a {
+        offsets.iter().filter_map(move |&d| self.step(p, d))
+    }
+
+    /// All positions in row-major order.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |k| (k % width, k / width))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn cells_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+            toroidal: self.toroidal,
+        }
+    }
+
+    /// The rows drawn as text, one character per cell.
+    pub fn render(&self, mut cell: impl FnMut(&T) -> char) -> Vec<String> {
+        if self.width == 0 {
+            return Vec::new();
+        }
+        self.cells.chunks(self.width).map(|row| row.iter().map(&mut cell).collect()).collect()
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.get(p).unwrap_or_else(|| panic!("{:?} is outside the {}×{} grid", p, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(p).unwrap_or_else(|| panic!("{:?} is outside the {}×{} grid", p, width, height))
+    }
+}
+
+#[test]
+fn grids_parse_and_render_char_maps() {
+    let grid = Grid::parse("#.\n..\n.#\n", |c| match c { '#' => Some(true), '.' => Some(false), _ => None }).unwrap();
+    assert_eq!((grid.width(), grid.height()), (2, 3));
+    assert!(grid[(1, 2)] && !grid[(0, 2)]);
+    assert_eq!(grid.render(|&b| if b { '#' } else { '.' }), vec!["#.", "..", ".#"]);
+    assert_eq!(Grid::parse("12\n3\n", |c| c.to_digit(10)), Err(GridError::RaggedRow(Span { line: 2, column: 1, length: 1 })));
+    assert_eq!(Grid::parse("12\n3x\n", |c| c.to_digit(10)), Err(GridError::InvalidCharacter('x', Span { line: 2, column: 2, length: 1 })));
+    assert_eq!(Grid::from_rows("\n", &["", ""], |c| c.to_digit(10)), Err(GridError::Empty));
+}
+
+#[test]
+fn positions_wrap_around_unless_there_are_no_cells() {
+    let grid = Grid::new(2, 1, vec![1, 2]);
+    assert_eq!(grid.wrapped(-1, 5), Some(&2));
+    assert_eq!(Grid::<u8>::new(0, 3, Vec::new()).wrapped(0, 0), None);
+}
+
+#[test]
+fn neighbours_stop_at_edges_unless_toroidal() {
+    let grid = Grid::filled(3, 3, 0);
+    assert_eq!(grid.neighbours((0, 0), &ORTHOGONAL).collect::<Vec<_>>(), vec![(1, 0), (0, 1)]);
+    assert_eq!(grid.neighbours((1, 1), &SURROUNDING).count(), 8);
+    let grid = grid.toroidal();
+    assert_eq!(grid.neighbours((0, 0), &ORTHOGONAL).collect::<Vec<_>>(), vec![(0, 2), (2, 0), (1, 0), (0, 1)]);
+}
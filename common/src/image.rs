@@ -0,0 +1,166 @@
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use crate::grid::{Grid, Point};
+use crate::Error;
+
+/// Side of a cell in SVG output, in pixels.
+const CELL: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Pbm,
+    Pgm,
+    Svg,
+    #[cfg(feature = "png")]
+    Png,
+}
+
+impl Format {
+    /// The format named by the extension of `path`.
+    pub fn of(path: &str) -> Option<Format> {
+        let extension = Path::new(path).extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "pbm" => Some(Format::Pbm),
+            "pgm" => Some(Format::Pgm),
+            "svg" => Some(Format::Svg),
+            #[cfg(feature = "png")]
+            "png" => Some(Format::Png),
+            _ => None,
+        }
+    }
+}
+
+/// A picture of a grid, with an ink level from 0 (blank) up to `max` (black)
+/// per cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    levels: Grid<u16>,
+    max: u16,
+}
+
+impl Image {
+    /// Lit cells are black, the others blank.
+    pub fn bitmap(grid: &Grid<bool>) -> Image {
+        Image { levels: grid.map(|&lit| lit as u16), max: 1 }
+    }
+
+    /// Shades of grey; the highest level of any cell is black.
+    pub fn graymap<T>(grid: &Grid<T>, level: impl FnMut(&T) -> u16) -> Image {
+        let levels = grid.map(level);
+        let max = levels.cells().copied().max().unwrap_or(0).max(1);
+        Image { levels, max }
+    }
+
+    /// A bitmap of the smallest grid from the origin holding every dot.
+    pub fn dots(dots: impl IntoIterator<Item = Point>) -> Image {
+        let dots: Vec<Point> = dots.into_iter().collect();
+        let width = dots.iter().map(|&(x, _)| x + 1).max().unwrap_or(0);
+        let height = dots.iter().map(|&(_, y)| y + 1).max().unwrap_or(0);
+        let mut grid = Grid::filled(width, height, false);
+        for dot in dots {
+            grid[dot] = true;
+        }
+        Image::bitmap(&grid)
+    }
+
+    fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = u16> + '_> + '_ {
+        (0..self.levels.height()).map(move |y| (0..self.levels.width()).map(move |x| self.levels[(x, y)]))
+    }
+
+    /// Plain PBM; cells at least half inked are black.
+    pub fn pbm(&self) -> String {
+        let mut text = format!("P1\n{} {}\n", self.levels.width(), self.levels.height());
+        for row in self.rows() {
+            let bits: Vec<&str> = row.map(|level| if 2 * level >= self.max { "1" } else { "0" }).collect();
+            text += &bits.join(" ");
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Plain PGM, white where there is no ink.
+    pub fn pgm(&self) -> String {
+        let mut text = format!("P2\n{} {}\n{}\n", self.levels.width(), self.levels.height(), self.max);
+        for row in self.rows() {
+            let values: Vec<String> = row.map(|level| (self.max - level).to_string()).collect();
+            text += &values.join(" ");
+            text.push('\n');
+        }
+        text
+    }
+
+    /// One square per inked cell on a white background.
+    pub fn svg(&self) -> String {
+        let (width, height) = (self.levels.width() * CELL, self.levels.height() * CELL);
+        let mut text = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n", width, height);
+        let _ = writeln!(text, "<rect width=\"{}\" height=\"{}\" fill=\"#fff\"/>", width, height);
+        for ((x, y), &level) in self.levels.iter().filter(|(_, &level)| level > 0) {
+            let grey = self.grey(level);
+            let _ = writeln!(text, "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#{:02x}{:02x}{:02x}\"/>",
+                x * CELL, y * CELL, CELL, CELL, grey, grey, grey);
+        }
+        text += "</svg>\n";
+        text
+    }
+
+    /// An 8-bit greyscale PNG.
+    #[cfg(feature = "png")]
+    pub fn png(&self) -> Result<Vec<u8>, Error> {
+        let failed = |error: png::EncodingError| Error::ImageEncodingFailed(error.to_string());
+        let mut data = Vec::new();
+        let mut encoder = png::Encoder::new(&mut data, self.levels.width() as u32, self.levels.height() as u32);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+        let pixels: Vec<u8> = self.rows().flatten().map(|level| self.grey(level)).collect();
+        let mut writer = encoder.write_header().map_err(failed)?;
+        writer.write_image_data(&pixels).map_err(failed)?;
+        writer.finish().map_err(failed)?;
+        Ok(data)
+    }
+
+    fn grey(&self, level: u16) -> u8 {
+        (255 - u32::from(level) * 255 / u32::from(self.max)) as u8
+    }
+
+    pub fn encode(&self, format: Format) -> Result<Vec<u8>, Error> {
+        match format {
+            Format::Pbm => Ok(self.pbm().into_bytes()),
+            Format::Pgm => Ok(self.pgm().into_bytes()),
+            Format::Svg => Ok(self.svg().into_bytes()),
+            #[cfg(feature = "png")]
+            Format::Png => self.png(),
+        }
+    }
+
+    /// Writes the image in the format named by the extension of `path`;
+    /// an image without pixels is refused rather than written in any format.
+    pub fn save(&self, path: &str) -> Result<(), Error> {
+        let format = Format::of(path).ok_or_else(|| Error::UnknownImageFormat(String::from(path)))?;
+        if self.levels.is_empty() {
+            return Err(Error::EmptyImage(String::from(path)));
+        }
+        fs::write(path, self.encode(format)?).map_err(|_| Error::FileWriteError(String::from(path)))
+    }
+}
+
+#[test]
+fn images_are_written_as_text_formats() {
+    let image = Image::dots([(0, 0), (2, 1)]);
+    assert_eq!(image.pbm(), "P1\n3 2\n1 0 0\n0 0 1\n");
+    let image = Image::graymap(&Grid::new(2, 1, vec![0, 4]), |&n| n);
+    assert_eq!(image.pgm(), "P2\n2 1\n4\n4 0\n");
+    assert_eq!(image.svg().matches("<rect").count(), 2);
+    assert_eq!(Format::of("out/day13.PBM"), Some(Format::Pbm));
+    assert_eq!(Format::of("day13.bmp"), None);
+    assert_eq!(Image::dots([]).save("empty.pbm"), Err(Error::EmptyImage(String::from("empty.pbm"))));
+}
+
+#[cfg(feature = "png")]
+#[test]
+fn images_are_written_as_png() {
+    let png = Image::dots([(0, 0), (2, 1)]).png().unwrap();
+    assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+    assert!(matches!(Image::dots([]).png(), Err(Error::ImageEncodingFailed(_))));
+}
@@ -0,0 +1,234 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use regex::Regex;
+
+mod diagnostic;
+pub mod animation;
+pub mod fixture;
+pub mod generate;
+pub mod grid;
+pub mod image;
+pub mod ocr;
+pub mod search;
+mod solution;
+
+pub use diagnostic::{Class, Diagnostic, Failure, Span};
+pub use animation::{Animation, RenderFrame};
+pub use generate::{Generated, Rng};
+pub use grid::{Grid, GridError};
+pub use image::Image;
+pub use search::Search;
+pub use solution::{main, print, reference, solve, Answer, References, Solution};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    FileReadError(String),
+    FileWriteError(String),
+    MissingArgument,
+    MissingValue(&'static str),
+    InvalidValue(&'static str, String),
+    UnknownImageFormat(String),
+    EmptyImage(String),
+    ImageEncodingFailed(String),
+    NothingToRender,
+    RenderNeedsOneInput,
+    NothingToAnimate,
+    AnimationFailed(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::FileReadError(name) => write!(f, "Failed to read from file: {}", name),
+            Error::FileWriteError(name) => write!(f, "Failed to write to file: {}", name),
+            Error::MissingArgument => write!(f, "Please specify input file."),
+            Error::MissingValue(option) => write!(f, "Option {} needs a value.", option),
+            Error::InvalidValue(option, value) => write!(f, "Invalid value for {}: {}", option, value),
+            Error::UnknownImageFormat(name) => write!(f, "Cannot tell the image format of {}; use .pbm, .pgm, .svg or, with the png feature, .png.", name),
+            Error::EmptyImage(name) => write!(f, "Nothing to draw in {}: the image has no pixels.", name),
+            Error::ImageEncodingFailed(reason) => write!(f, "Failed to encode image: {}", reason),
+            Error::NothingToRender => write!(f, "This puzzle has nothing to render."),
+            Error::RenderNeedsOneInput => write!(f, "Options --render and --animate take a single input."),
+            Error::NothingToAnimate => write!(f, "This puzzle has nothing to animate."),
+            Error::AnimationFailed(reason) => write!(f, "Animation failed: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::FileReadError(_) | Error::FileWriteError(_) => Class::Input,
+            Error::MissingArgument => Class::Usage,
+            Error::MissingValue(_) => Class::Usage,
+            Error::InvalidValue(..) => Class::Usage,
+            Error::UnknownImageFormat(_) => Class::Usage,
+            Error::EmptyImage(_) => Class::Input,
+            Error::ImageEncodingFailed(_) => Class::Input,
+            Error::NothingToRender => Class::Usage,
+            Error::RenderNeedsOneInput => Class::Usage,
+            Error::NothingToAnimate => Class::Usage,
+            Error::AnimationFailed(_) => Class::Input,
+        }
+    }
+}
+
+/// Converts an `Option` or a foreign `Result` into a `Result` with the given error.
+pub trait ToError<T,E> {
+    fn check(self, error: E) -> Result<T,E>;
+}
+
+impl<T,E> ToError<T,E> for Option<T> {
+    fn check(self, error: E) -> Result<T,E> {
+        self.ok_or(error)
+    }
+}
+
+impl<T,E,_E> ToError<T,E> for Result<T,_E> {
+    fn check(self, error: E) -> Result<T,E> {
+        self.ok().check(error)
+    }
+}
+
+/// The command line of a puzzle: its input files, where to write its
+/// picture and how to animate it, if at all.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub files: Vec<String>,
+    pub render: Option<String>,
+    pub animation: Option<Animation>,
+}
+
+/// Reads `[--render <path>] [--animate] [--fps <n>] [--frames <dir>]
+/// <input>...`; `--frames` animates headless into a directory. Inputs are
+/// expanded by [`expand`].
+pub fn options(args: impl IntoIterator<Item = String>) -> Result<Options, Error> {
+    let mut args = args.into_iter();
+    let mut names = Vec::new();
+    let mut render = None;
+    let mut animate = false;
+    let mut fps = 10.0;
+    let mut dir = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--render" => render = Some(args.next().ok_or(Error::MissingValue("--render"))?),
+            "--animate" => animate = true,
+            "--fps" => {
+                let value = args.next().ok_or(Error::MissingValue("--fps"))?;
+                fps = value.parse().ok().filter(|&fps: &f64| fps > 0.0)
+                    .ok_or(Error::InvalidValue("--fps", value))?;
+            },
+            "--frames" => dir = Some(args.next().ok_or(Error::MissingValue("--frames"))?.into()),
+            _ => names.push(arg),
+        }
+    }
+    if names.is_empty() {
+        return Err(Error::MissingArgument);
+    }
+    let animation = (animate || dir.is_some()).then_some(Animation { fps, dir });
+    Ok(Options { files: expand(names), render, animation })
+}
+
+/// The input files named on the command line; see [`expand`].
+pub fn filenames() -> Result<Vec<String>, Error> {
+    Ok(options(env::args().skip(1))?.files)
+}
+
+/// Replaces every directory among `names` by the files in it, in order.
+pub fn expand(names: Vec<String>) -> Vec<String> {
+    let mut result = Vec::new();
+    for name in names {
+        match fs::read_dir(&name) {
+            Ok(entries) => {
+                let mut files: Vec<String> = entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.path())
+                    .filter(|path| path.is_file())
+                    .map(|path| path.to_string_lossy().into_owned())
+                    .collect();
+                files.sort();
+                result.extend(files);
+            },
+            Err(_) => result.push(name),
+        }
+    }
+    result
+}
+
+/// The name an input is reported under; `-` stands for stdin.
+pub fn label(filename: &str) -> &str {
+    if filename == "-" { "<stdin>" } else { filename }
+}
+
+/// The entire contents of a file, or of stdin if the name is `-`.
+pub fn read(filename: &str) -> Result<String, Error> {
+    if filename != "-" {
+        return fread(filename);
+    }
+    let mut text = String::new();
+    io::stdin().read_to_string(&mut text)
+        .check(Error::FileReadError(String::from(label(filename))))?;
+    Ok(text)
+}
+
+/// The entire contents of a file.
+pub fn fread(filename: &str) -> Result<String, Error> {
+    fs::read_to_string(filename)
+        .check(Error::FileReadError(String::from(filename)))
+}
+
+/// The trimmed, non-empty lines of a text, as slices of it.
+pub fn trimmed(text: &str) -> Vec<&str> {
+    text.split('\n')
+        .map(|x| x.trim())
+        .filter(|x| !x.is_empty())
+        .collect()
+}
+
+/// The trimmed, non-empty lines of a file.
+pub fn lines(filename: &str) -> Result<Vec<String>, Error> {
+    Ok(trimmed(&fread(filename)?).into_iter().map(String::from).collect())
+}
+
+/// Splits a text into blocks separated by blank lines.
+pub fn paragraphs(text: &str) -> Vec<&str> {
+    Regex::new(r"\n\s*\n").unwrap().split(text).collect()
+}
+
+#[test]
+fn options_take_a_render_path() {
+    let args = |list: &[&str]| options(list.iter().map(|s| s.to_string()));
+    assert_eq!(args(&["-", "--render", "out.svg"]).map(|o| o.render), Ok(Some(String::from("out.svg"))));
+    assert_eq!(args(&["--frames", "out", "-"]).map(|o| o.animation), Ok(Some(Animation { fps: 10.0, dir: Some("out".into()) })));
+    assert_eq!(args(&["--fps", "0", "-"]), Err(Error::InvalidValue("--fps", String::from("0"))));
+    assert_eq!(args(&["-", "--render"]), Err(Error::MissingValue("--render")));
+    assert_eq!(args(&[]), Err(Error::MissingArgument));
+}
+
+#[test]
+fn expand_lists_directories_in_order() {
+    let dir = env::temp_dir().join(format!("aoc-expand-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    for name in ["b.txt", "a.txt"] {
+        fs::write(dir.join(name), "").unwrap();
+    }
+    let names = expand(vec![String::from("-"), dir.to_string_lossy().into_owned()]);
+    fs::remove_dir_all(&dir).unwrap();
+    let a = dir.join("a.txt").to_string_lossy().into_owned();
+    let b = dir.join("b.txt").to_string_lossy().into_owned();
+    assert_eq!(names, vec![String::from("-"), a, b]);
+}
+
+#[test]
+fn trimmed_skips_blank_lines() {
+    assert_eq!(trimmed(" a \n\n b\r\n"), vec!["a", "b"]);
+}
+
+#[test]
+fn paragraphs_split_on_blank_lines() {
+    assert_eq!(paragraphs("a\nb\n\nc\n  \nd"), vec!["a\nb", "c", "d"]);
+}
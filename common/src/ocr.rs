@@ -0,0 +1,169 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::grid::Point;
+use crate::{Class, Diagnostic};
+
+/// The capital letters of the puzzles' 4×6 font.
+const SMALL: [(char, [&str; 6]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// The capital letters of the larger 6×10 font.
+const LARGE: [(char, [&str; 10]); 15] = [
+    ('A', ["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', ["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', [".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', [".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', ["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', ["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', ["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', ["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', ["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', ["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', ["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', ["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', ["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+/// Why the lit pixels cannot be read as letters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrError {
+    /// The lit rows are neither 6 nor 10 high.
+    UnsupportedHeight(usize),
+    /// The `index`th glyph, starting at `column`, is no known letter.
+    UnknownGlyph { index: usize, column: usize },
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OcrError::UnsupportedHeight(h) => write!(f, "Letters are {} rows high instead of 6 or 10.", h),
+            OcrError::UnknownGlyph { index, column } =>
+                write!(f, "Letter {} at column {} is not recognised.", index + 1, column + 1),
+        }
+    }
+}
+
+impl Diagnostic for OcrError {
+    fn class(&self) -> Class {
+        Class::Solve
+    }
+}
+
+fn lit(c: char) -> bool {
+    !matches!(c, ' ' | '.')
+}
+
+/// Reads the letters drawn in `rows`, where any character other than a
+/// space or `.` is lit. Letters are told apart by the blank columns between
+/// them; blank rows above and below are ignored.
+pub fn recognise<S: AsRef<str>>(rows: &[S]) -> Result<String, OcrError> {
+    let rows: Vec<Vec<bool>> = rows.iter().map(|row| row.as_ref().chars().map(lit).collect()).collect();
+    let Some(top) = rows.iter().position(|row| row.contains(&true)) else {
+        return Ok(String::new());
+    };
+    let bottom = rows.iter().rposition(|row| row.contains(&true)).unwrap_or(top);
+    let rows = &rows[top..=bottom];
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let column = |x: usize| -> Vec<bool> { rows.iter().map(|row| row.get(x) == Some(&true)).collect() };
+
+    let font: Vec<(char, Vec<Vec<bool>>)> = match rows.len() {
+        6 => SMALL.iter().map(|(c, glyph)| (*c, glyph_columns(glyph))).collect(),
+        10 => LARGE.iter().map(|(c, glyph)| (*c, glyph_columns(glyph))).collect(),
+        h => return Err(OcrError::UnsupportedHeight(h)),
+    };
+
+    let mut result = String::new();
+    let mut x = 0;
+    while x < width {
+        if !column(x).contains(&true) {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        let mut glyph = Vec::new();
+        while x < width && column(x).contains(&true) {
+            glyph.push(column(x));
+            x += 1;
+        }
+        let letter = font.iter().find(|(_, columns)| *columns == glyph)
+            .ok_or(OcrError::UnknownGlyph { index: result.chars().count(), column: start })?;
+        result.push(letter.0);
+    }
+    Ok(result)
+}
+
+/// Reads the letters formed by a set of lit `(x, y)` positions.
+pub fn recognise_dots(dots: impl IntoIterator<Item = Point>) -> Result<String, OcrError> {
+    let dots: HashSet<Point> = dots.into_iter().collect();
+    let width = dots.iter().map(|&(x, _)| x + 1).max().unwrap_or(0);
+    let height = dots.iter().map(|&(_, y)| y + 1).max().unwrap_or(0);
+    let rows: Vec<String> = (0..height).map(|y| {
+        (0..width).map(|x| if dots.contains(&(x, y)) { '#' } else { '.' }).collect()
+    }).collect();
+    recognise(&rows)
+}
+
+/// Draws `text` in the 4×6 font with a blank column after every letter, the
+/// way the puzzles do; `None` if a letter is not in the font.
+pub fn draw(text: &str) -> Option<Vec<String>> {
+    let mut rows = vec![String::new(); 6];
+    for c in text.chars() {
+        let (_, glyph) = SMALL.iter().find(|(letter, _)| *letter == c)?;
+        for (row, line) in rows.iter_mut().zip(glyph) {
+            *row += line;
+            row.push('.');
+        }
+    }
+    Some(rows)
+}
+
+/// The letters [`draw`] can write.
+pub fn letters() -> impl Iterator<Item = char> {
+    SMALL.iter().map(|(c, _)| *c)
+}
+
+/// The columns of a glyph from the font, left to right.
+fn glyph_columns(rows: &[&str]) -> Vec<Vec<bool>> {
+    let width = rows[0].len();
+    (0..width).map(|x| rows.iter().map(|row| row.as_bytes()[x] == b'#').collect()).collect()
+}
+
+#[test]
+fn both_fonts_are_read() {
+    let small = ["#..#.####.###..", "#..#.#....#..#.", "####.###..###..", "#..#.#....#..#.", "#..#.#....#..#.", "#..#.####.###.."];
+    assert_eq!(recognise(&small), Ok(String::from("HEB")));
+    let large: Vec<String> = (0..10).map(|y| format!("{}..{}", LARGE[13].1[y], LARGE[9].1[y])).collect();
+    assert_eq!(recognise(&large), Ok(String::from("XL")));
+    let dots = (0..6).flat_map(|y| [(0, y), (3, y)]).chain([(1, 2), (2, 2)]);
+    assert_eq!(recognise_dots(dots), Ok(String::from("H")));
+    let word: String = letters().collect();
+    assert_eq!(recognise(&draw(&word).unwrap()), Ok(word));
+}
+
+#[test]
+fn unknown_glyphs_are_located() {
+    let rows = ["#..#..#", "#..#.##", "####..#", "#..#..#", "#..#..#", "#..#.##"];
+    assert_eq!(recognise(&rows), Err(OcrError::UnknownGlyph { index: 1, column: 5 }));
+    assert_eq!(recognise(&["#", "#"]), Err(OcrError::UnsupportedHeight(2)));
+}
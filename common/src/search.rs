@@ -0,0 +1,355 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+use crate::grid::{Grid, Point};
+
+/// The cheapest way found to a goal: its total cost and every node from the
+/// start to the goal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N> {
+    pub cost: usize,
+    pub nodes: Vec<N>,
+}
+
+/// Remembers the cheapest cost seen for each node, so that a node is only
+/// expanded once. A `HashMap` works for any hashable node, a grid of
+/// `Option<usize>` is a dense alternative for grid positions, and [`Revisit`]
+/// keeps no record at all.
+pub trait Visited<N> {
+    /// Records `cost` for `node` and returns `true` if it beats every cost
+    /// seen for it before.
+    fn improve(&mut self, node: &N, cost: usize) -> bool;
+
+    /// Whether `cost` is still the best one known for `node`.
+    fn is_best(&self, node: &N, cost: usize) -> bool;
+}
+
+/// Visits every node as often as it is reached, which enumerates every path
+/// of an acyclic state space.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Revisit;
+
+impl<N: Clone + Eq + Hash> Visited<N> for HashMap<N, usize> {
+    fn improve(&mut self, node: &N, cost: usize) -> bool {
+        match self.get(node) {
+            Some(&best) if best <= cost => false,
+            _ => {
+                self.insert(node.clone(), cost);
+                true
+            },
+        }
+    }
+
+    fn is_best(&self, node: &N, cost: usize) -> bool {
+        self.get(node) == Some(&cost)
+    }
+}
+
+impl Visited<Point> for Grid<Option<usize>> {
+    fn improve(&mut self, &node: &Point, cost: usize) -> bool {
+        match self.get_mut(node) {
+            Some(Some(best)) if *best <= cost => false,
+            Some(best) => {
+                *best = Some(cost);
+                true
+            },
+            None => false,
+        }
+    }
+
+    fn is_best(&self, &node: &Point, cost: usize) -> bool {
+        self.get(node) == Some(&Some(cost))
+    }
+}
+
+impl<N> Visited<N> for Revisit {
+    fn improve(&mut self, _: &N, _: usize) -> bool {
+        true
+    }
+
+    fn is_best(&self, _: &N, _: usize) -> bool {
+        true
+    }
+}
+
+enum Open {
+    Fifo(VecDeque<usize>),
+    Stack(Vec<usize>),
+    Heap(BinaryHeap<Reverse<(usize, usize)>>),
+    Buckets(Vec<Vec<usize>>, usize),
+}
+
+impl Open {
+    fn push(&mut self, priority: usize, entry: usize) {
+        match self {
+            Open::Fifo(queue) => queue.push_back(entry),
+            Open::Stack(stack) => stack.push(entry),
+            Open::Heap(heap) => heap.push(Reverse((priority, entry))),
+            Open::Buckets(buckets, cursor) => {
+                let priority = priority.max(*cursor);
+                if buckets.len() <= priority {
+                    buckets.resize_with(priority + 1, Vec::new);
+                }
+                buckets[priority].push(entry);
+            },
+        }
+    }
+
+    fn pop(&mut self) -> Option<usize> {
+        match self {
+            Open::Fifo(queue) => queue.pop_front(),
+            Open::Stack(stack) => stack.pop(),
+            Open::Heap(heap) => heap.pop().map(|Reverse((_, entry))| entry),
+            Open::Buckets(buckets, cursor) => loop {
+                let bucket = buckets.get_mut(*cursor)?;
+                match bucket.pop() {
+                    Some(entry) => return Some(entry),
+                    None => *cursor += 1,
+                }
+            },
+        }
+    }
+}
+
+struct Entry<N> {
+    node: N,
+    cost: usize,
+    parent: Option<usize>,
+}
+
+/// A best-first search over an implicit graph, given as a function from a
+/// node to its successors and the cost of the step to each.
+///
+/// By default this is Dijkstra's algorithm on a binary heap; it becomes A*
+/// with a [`heuristic`](Search::heuristic), a breadth-first search with
+/// [`breadth_first`](Search::breadth_first) when all steps cost the same,
+/// and uses a bucket queue with [`buckets`](Search::buckets), which suits
+/// small integer costs. With [`depth_first`](Search::depth_first) and
+/// [`forget_paths`](Search::forget_paths) it walks the paths of a state
+/// space one at a time, in memory bounded by their length. As an iterator
+/// it yields each node with its cost in the order the nodes are expanded,
+/// so the search stops as soon as the caller does.
+pub struct Search<N, S, V = HashMap<N, usize>, H = fn(&N) -> usize> {
+    starts: Vec<N>,
+    successors: S,
+    visited: V,
+    heuristic: H,
+    open: Open,
+    entries: Vec<Entry<N>>,
+    free: Option<Vec<usize>>,
+    last: Option<usize>,
+}
+
+impl<N, S, I> Search<N, S>
+where
+    N: Clone + Eq + Hash,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    pub fn new(start: N, successors: S) -> Self {
+        Search::from_starts(vec![start], successors)
+    }
+
+    /// A search that begins at all of `starts` at once.
+    pub fn from_starts(starts: Vec<N>, successors: S) -> Self {
+        Search {
+            starts,
+            successors,
+            visited: HashMap::new(),
+            heuristic: |_| 0,
+            open: Open::Heap(BinaryHeap::new()),
+            entries: Vec::new(),
+            free: None,
+            last: None,
+        }
+    }
+}
+
+impl<N, S, I, V, H> Search<N, S, V, H>
+where
+    N: Clone,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, usize)>,
+    V: Visited<N>,
+    H: FnMut(&N) -> usize,
+{
+    /// Keeps track of visited nodes with `visited` instead of a `HashMap`.
+    pub fn visited<W: Visited<N>>(self, visited: W) -> Search<N, S, W, H> {
+        Search {
+            starts: self.starts,
+            successors: self.successors,
+            visited,
+            heuristic: self.heuristic,
+            open: self.open,
+            entries: self.entries,
+            free: self.free,
+            last: self.last,
+        }
+    }
+
+    /// Guides the search with an estimate of the remaining cost to the
+    /// goal, which must never overestimate it.
+    pub fn heuristic<G: FnMut(&N) -> usize>(self, heuristic: G) -> Search<N, S, V, G> {
+        Search {
+            starts: self.starts,
+            successors: self.successors,
+            visited: self.visited,
+            heuristic,
+            open: self.open,
+            entries: self.entries,
+            free: self.free,
+            last: self.last,
+        }
+    }
+
+    /// Expands nodes first in first out; only correct if every step costs
+    /// the same.
+    pub fn breadth_first(mut self) -> Self {
+        self.open = Open::Fifo(VecDeque::new());
+        self
+    }
+
+    /// Keeps the open nodes in one bucket per priority instead of a heap.
+    pub fn buckets(mut self) -> Self {
+        self.open = Open::Buckets(Vec::new(), 0);
+        self
+    }
+
+    /// Expands the node reached last first, ignoring costs; with
+    /// [`Revisit`] this follows one path to its end before the next.
+    pub fn depth_first(mut self) -> Self {
+        self.open = Open::Stack(Vec::new());
+        self
+    }
+
+    /// Drops every node once it has been expanded, so that only the open
+    /// nodes are kept; [`path`](Search::path) then holds just the node
+    /// yielded last.
+    pub fn forget_paths(mut self) -> Self {
+        self.free = Some(Vec::new());
+        self
+    }
+
+    fn push(&mut self, node: N, cost: usize, parent: Option<usize>) {
+        if self.visited.improve(&node, cost) {
+            let priority = cost + (self.heuristic)(&node);
+            let (parent, slot) = match &mut self.free {
+                Some(free) => (None, free.pop()),
+                None => (parent, None),
+            };
+            let entry = Entry { node, cost, parent };
+            let index = match slot {
+                Some(index) => {
+                    self.entries[index] = entry;
+                    index
+                },
+                None => {
+                    self.entries.push(entry);
+                    self.entries.len() - 1
+                },
+            };
+            self.open.push(priority, index);
+        }
+    }
+
+    fn release(&mut self, entry: usize) {
+        if let Some(free) = &mut self.free {
+            free.push(entry);
+        }
+    }
+
+    fn expand(&mut self) -> Option<usize> {
+        for start in std::mem::take(&mut self.starts) {
+            self.push(start, 0, None);
+        }
+        if let Some(last) = self.last.take() {
+            self.release(last);
+        }
+        let entry = loop {
+            let entry = self.open.pop()?;
+            let Entry { node, cost, .. } = &self.entries[entry];
+            if self.visited.is_best(node, *cost) {
+                break entry;
+            }
+            self.release(entry);
+        };
+        let cost = self.entries[entry].cost;
+        for (next, step) in (self.successors)(&self.entries[entry].node) {
+            self.push(next, cost + step, Some(entry));
+        }
+        self.last = Some(entry);
+        Some(entry)
+    }
+
+    /// The nodes from a start to the node yielded last.
+    pub fn path(&self) -> Vec<N> {
+        let mut nodes = Vec::new();
+        let mut current = self.last;
+        while let Some(entry) = current {
+            nodes.push(self.entries[entry].node.clone());
+            current = self.entries[entry].parent;
+        }
+        nodes.reverse();
+        nodes
+    }
+
+    /// Searches until a node satisfies `goal` and returns the path to it.
+    pub fn find(&mut self, mut goal: impl FnMut(&N) -> bool) -> Option<Path<N>> {
+        while let Some(entry) = self.expand() {
+            if goal(&self.entries[entry].node) {
+                return Some(Path { cost: self.entries[entry].cost, nodes: self.path() });
+            }
+        }
+        None
+    }
+}
+
+impl<N, S, I, V, H> Iterator for Search<N, S, V, H>
+where
+    N: Clone,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, usize)>,
+    V: Visited<N>,
+    H: FnMut(&N) -> usize,
+{
+    type Item = (N, usize);
+
+    fn next(&mut self) -> Option<(N, usize)> {
+        let entry = self.expand()?;
+        Some((self.entries[entry].node.clone(), self.entries[entry].cost))
+    }
+}
+
+#[cfg(test)]
+fn steps(&n: &u32) -> Vec<(u32, usize)> {
+    match n {
+        0 => vec![(1, 7), (2, 2)],
+        1 => vec![(3, 1)],
+        2 => vec![(1, 3), (3, 8)],
+        _ => vec![],
+    }
+}
+
+#[test]
+fn every_queue_finds_the_cheapest_path() {
+    let expected = Some(Path { cost: 6, nodes: vec![0, 2, 1, 3] });
+    assert_eq!(Search::new(0, steps).find(|&n| n == 3), expected);
+    assert_eq!(Search::new(0, steps).buckets().find(|&n| n == 3), expected);
+    assert_eq!(Search::new(0, steps).heuristic(|&n| if n == 3 { 0 } else { 1 }).find(|&n| n == 3), expected);
+    let hops = |n: &u32| steps(n).into_iter().map(|(m, _)| (m, 1));
+    assert_eq!(Search::new(0, hops).breadth_first().collect::<Vec<_>>(), vec![(0, 0), (1, 1), (2, 1), (3, 2)]);
+}
+
+#[test]
+fn revisiting_enumerates_paths() {
+    assert_eq!(Search::new(0, steps).visited(Revisit).filter(|&(n, _)| n == 3).count(), 3);
+    let mut walk = Search::new(0, steps).visited(Revisit).depth_first().forget_paths();
+    assert_eq!(walk.by_ref().filter(|&(n, _)| n == 3).count(), 3);
+    assert!(walk.entries.len() <= 4);
+    let grid: Grid<Option<usize>> = Grid::filled(3, 3, None);
+    let corner = Search::new((0, 0), |&(x, y): &Point| [((x + 1, y), 1), ((x, y + 1), 1)])
+        .visited(grid)
+        .find(|&p| p == (2, 2));
+    assert_eq!(corner.map(|path| path.cost), Some(4));
+}
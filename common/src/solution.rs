@@ -0,0 +1,183 @@
+use std::env;
+use std::fmt;
+use std::process;
+
+use tracing::info_span;
+
+use crate::{label, options, read, Animation, Class, Diagnostic, Error, Failure, Generated, Image, RenderFrame, Rng};
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+    /// Multi-line ASCII art, one string per row.
+    Art(Vec<String>),
+}
+
+impl Answer {
+    pub fn art<I, S>(rows: I) -> Answer
+    where I: IntoIterator<Item = S>, S: Into<String> {
+        Answer::Art(rows.into_iter().map(Into::into).collect())
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => f.write_str(s),
+            Answer::Art(rows) => f.write_str(&rows.join("\n")),
+        }
+    }
+}
+
+macro_rules! number {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::Number(n as i128)
+            }
+        })*
+    };
+}
+
+number!(u8, u16, u32, u64, usize, i8, i16, i32, i64, i128, isize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+/// The answers of a reference method; `None` for a part it cannot solve.
+pub type References = [Option<Answer>; 2];
+
+/// A puzzle split into a parsing stage and two solving stages.
+pub trait Solution {
+    type Input;
+    type Error: Diagnostic;
+    type Part1: Into<Answer>;
+    type Part2: Into<Answer>;
+
+    fn parse(input: &str) -> Result<Self::Input, Self::Error>;
+    fn part1(input: &Self::Input) -> Result<Self::Part1, Self::Error>;
+    fn part2(input: &Self::Input) -> Result<Self::Part2, Self::Error>;
+
+    /// A picture of the puzzle for `--render`, if it has one.
+    fn render(_input: &Self::Input) -> Result<Option<Image>, Self::Error> {
+        Ok(None)
+    }
+
+    /// The simulation to show for `--animate`, if there is one.
+    fn animate(_input: &Self::Input) -> Option<Box<dyn RenderFrame>> {
+        None
+    }
+
+    /// A random valid input for stress tests, if the day can make one.
+    fn generate(_rng: &mut Rng) -> Option<Generated> {
+        None
+    }
+
+    /// The answers found by a slow but plainly correct method, for the parts
+    /// that have one, to test the solver against; `None` if the day has no
+    /// such method.
+    fn reference(_input: &Self::Input) -> Option<References> {
+        None
+    }
+
+    /// Inputs a step simpler than `text` other than by dropping lines, for
+    /// shrinking one that fails: each must be shorter, or as long with
+    /// smaller numbers, so that shrinking ends.
+    fn simplify(_text: &str) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// Parses the text and solves both parts.
+pub fn solve<S: Solution>(text: &str) -> Result<[Answer; 2], S::Error> {
+    let input = info_span!("parse").in_scope(|| S::parse(text))?;
+    Ok([
+        info_span!("part1").in_scope(|| S::part1(&input))?.into(),
+        info_span!("part2").in_scope(|| S::part2(&input))?.into(),
+    ])
+}
+
+/// Parses the text and finds the answers of the reference method.
+pub fn reference<S: Solution>(text: &str) -> Result<Option<References>, S::Error> {
+    Ok(S::reference(&S::parse(text)?))
+}
+
+/// Prints an answer; art starts on its own line.
+pub fn print(part: usize, answer: &Answer) {
+    match answer {
+        Answer::Art(_) => println!("Part {}:\n{}", part, answer),
+        _ => println!("Part {}: {}", part, answer),
+    }
+}
+
+fn run<S: Solution>(file: &str, render: Option<&str>, animation: Option<&Animation>) -> Result<(), Class> {
+    let text = read(file).map_err(|e| Failure::new(&e).report("", ""))?;
+    let failed = |e: S::Error| Failure::new(&e).report(label(file), &text);
+    let input = info_span!("parse").in_scope(|| S::parse(&text)).map_err(failed)?;
+    if let Some(animation) = animation {
+        let mut simulation = S::animate(&input).ok_or(Error::NothingToAnimate)
+            .map_err(|e| Failure::new(&e).report("", ""))?;
+        animation.play(simulation.as_mut())
+            .map_err(|e| Failure::new(&Error::AnimationFailed(e.to_string())).report("", ""))?;
+    }
+    let answers: [Answer; 2] = [
+        info_span!("part1").in_scope(|| S::part1(&input)).map_err(failed)?.into(),
+        info_span!("part2").in_scope(|| S::part2(&input)).map_err(failed)?.into(),
+    ];
+    for (k, answer) in answers.iter().enumerate() {
+        print(k + 1, answer);
+    }
+    if let Some(path) = render {
+        let image = S::render(&input).map_err(failed)?;
+        image.ok_or(Error::NothingToRender)
+            .and_then(|image| image.save(path))
+            .map_err(|e| Failure::new(&e).report("", ""))?;
+    }
+    Ok(())
+}
+
+/// Solves the puzzle for every input named on the command line and prints
+/// both answers, under a heading per input if there are several. With
+/// `--render <path>` the picture of a single input is also written to the
+/// path, and with `--animate` or `--frames` its simulation is played first.
+/// Errors are reported on stderr; the first one sets the exit status.
+pub fn main<S: Solution>() {
+    let exit = |e: Error| -> ! { process::exit(Failure::new(&e).report("", "").exit_code()) };
+    let options = options(env::args().skip(1)).unwrap_or_else(|e| exit(e));
+    let files = options.files;
+    if (options.render.is_some() || options.animation.is_some()) && files.len() > 1 {
+        exit(Error::RenderNeedsOneInput);
+    }
+    let mut failed: Option<Class> = None;
+    for file in &files {
+        if files.len() > 1 {
+            println!("== {} ==", label(file));
+        }
+        if let Err(class) = run::<S>(file, options.render.as_deref(), options.animation.as_ref()) {
+            failed = failed.or(Some(class));
+        }
+    }
+    if let Some(class) = failed {
+        process::exit(class.exit_code());
+    }
+}
+
+#[test]
+fn answers_display_like_their_values() {
+    assert_eq!(Answer::from(42usize).to_string(), "42");
+    assert_eq!(Answer::from(-7i64).to_string(), "-7");
+    assert_eq!(Answer::from("CBA").to_string(), "CBA");
+    assert_eq!(Answer::art(["#.", ".#"]).to_string(), "#.\n.#");
+}
@@ -0,0 +1,14 @@
+//! `PasswordEntry::new` must turn any input into an error rather than panic,
+//! and so must both parts for whatever it accepts.
+#![no_main]
+
+use aoc_common::Solution;
+use aoc_2020_day02::Day02;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    if let Ok(input) = Day02::parse(text) {
+        let _ = Day02::part1(&input);
+        let _ = Day02::part2(&input);
+    }
+});
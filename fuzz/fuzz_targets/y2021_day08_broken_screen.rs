@@ -0,0 +1,14 @@
+//! `BrokenScreen::new` must turn any input into an error rather than panic,
+//! and so must both parts for whatever it accepts.
+#![no_main]
+
+use aoc_common::Solution;
+use aoc_2021_day08::Day08;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    if let Ok(input) = Day08::parse(text) {
+        let _ = Day08::part1(&input);
+        let _ = Day08::part2(&input);
+    }
+});
@@ -1,45 +1,52 @@
-use std::fs;
-use std::env;
-
-#[derive(Debug, Clone)]
-enum Error {
-    FileReadError(String),
-    MissingArgument
-}
-
-fn lines(filename: &str) -> Result<Vec<String>, Error> {
-    Ok(fs::read_to_string(filename)
-        .map_err(|_| Error::FileReadError(String::from(filename)))?
-        .split('\n')
-        .map(|x| String::from(x.trim()))
-        .filter(|x| x.len() > 0)
-        .collect::<Vec<_>>())
-}
-
-fn filename() -> Result<String, Error> {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        Err(Error::MissingArgument)
-    } else {
-        Ok(args[1].clone())
-    }
-}
-
-fn main_or_error() -> Result<(), Error> {
-    let filename = filename()?;
-    let lines = lines(&filename)?;
-    
-    Ok(())
-} 
-
-fn main() {
-    match main_or_error() {
-        Ok(_) => (),
-        Err(Error::MissingArgument) => {
-            println!("Please specify input file.");
-        },
-        Err(Error::FileReadError(name)) => {
-            println!("Failed to read from file: {}", name);
-        },
-    }
-}
+//! Advent of Code YEAR, day DAY.
+
+#![warn(missing_docs)]
+
+use std::fmt;
+
+use aoc_common::Class;
+
+/// Why the puzzle cannot be solved.
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// The puzzle is still to be done.
+    Unsolved,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Unsolved => write!(f, "Not solved yet."),
+        }
+    }
+}
+
+impl aoc_common::Diagnostic for Error {
+    fn class(&self) -> Class {
+        match self {
+            Error::Unsolved => Class::Solve,
+        }
+    }
+}
+
+/// Solves both parts of the puzzle.
+pub struct DayNN;
+
+impl aoc_common::Solution for DayNN {
+    type Input = Vec<String>;
+    type Error = Error;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<String>, Error> {
+        Ok(aoc_common::trimmed(input).into_iter().map(String::from).collect())
+    }
+
+    fn part1(_lines: &Vec<String>) -> Result<usize, Error> {
+        Err(Error::Unsolved)
+    }
+
+    fn part2(_lines: &Vec<String>) -> Result<usize, Error> {
+        Err(Error::Unsolved)
+    }
+}